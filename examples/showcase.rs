@@ -63,12 +63,13 @@ fn create_cursors_page() -> Box<Control> {
     ];
 
     let mut radios = SmallVec::<[Toggle; 32]>::new();
-    for &(text, cur) in radios_info.iter() {
+    for &(text, ref cur) in radios_info.iter() {
         let toggle = Toggle::new();
         toggle.set_title(text);
         let cursors_canvas2 = cursors_canvas.clone();
+        let cur = cur.clone();
         toggle.action_event().add(move |checked| {
-            if checked { cursors_canvas2.set_cursor(cur); }
+            if checked { cursors_canvas2.set_cursor(cur.clone()); }
         });
         radios.push(toggle);
     }
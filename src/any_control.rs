@@ -0,0 +1,177 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::ffi::CStr;
+use super::control_prelude::*;
+use super::{
+    Button,
+    Canvas,
+    Clipboard,
+    Dialog,
+    FileDlg,
+    Fill,
+    Frame,
+    GridBox,
+    Hbox,
+    Image,
+    Item,
+    Label,
+    List,
+    Menu,
+    Radio,
+    Separator,
+    Submenu,
+    Tabs,
+    Text,
+    Timer,
+    Toggle,
+    Vbox,
+};
+
+// Returns the name of the control's class, such as "button" or "dialog".
+pub fn get_class_name(ih: *mut Ihandle) -> String {
+    unsafe {
+        CStr::from_ptr(IupGetClassName(ih)).to_string_lossy().into_owned()
+    }
+}
+
+// Wraps a control that was looked up by `name`, where `ih` is null if it wasn't found.
+pub fn wrap_named<T: ControlType>(ih: *mut Ihandle, name: &str) -> Result<T, Error> {
+    if ih.is_null() {
+        return Err(Error::NameNotFound { name: name.to_owned() });
+    }
+    unsafe {
+        T::try_from_handle(ih).ok_or_else(|| Error::WrongClass {
+            name: name.to_owned(),
+            expected: T::CLASS_NAMES[0],
+            actual: get_class_name(ih),
+        })
+    }
+}
+
+/// Implemented by the wrapper for each class of IUP control, so that it can be created from the
+/// handle of an existing control.
+pub unsafe trait ControlType : Control + Sized {
+    /// The class names (as returned by `IupGetClassName`) of the controls this type wraps.
+    const CLASS_NAMES: &'static [&'static str];
+
+    /// Wraps an existing control without checking its class.
+    unsafe fn from_handle_unchecked(ih: *mut Ihandle) -> Self;
+
+    /// Wraps an existing control, or returns `None` if it isn't one of `CLASS_NAMES`.
+    unsafe fn try_from_handle(ih: *mut Ihandle) -> Option<Self> {
+        ::iup_open();
+        if Self::CLASS_NAMES.contains(&&*get_class_name(ih)) {
+            Some(Self::from_handle_unchecked(ih))
+        } else {
+            None
+        }
+    }
+}
+
+/// A control whose class doesn't have a wrapper in this library.
+#[derive(Clone)]
+pub struct UnknownControl(HandleRc);
+
+impl UnknownControl {
+    pub fn class_name(&self) -> String {
+        get_class_name(self.handle())
+    }
+}
+
+impl_control_traits!(UnknownControl);
+
+macro_rules! any_control {
+    ($($variant:ident),*) => {
+        /// Any control, wrapped by the type for its class.
+        ///
+        /// This is returned when getting existing controls, such as from `Control::parent` or
+        /// `Container::children`, so that generic code can find out the type of each one.
+        #[derive(Clone)]
+        pub enum AnyControl {
+            $($variant($variant),)*
+            Unknown(UnknownControl),
+        }
+
+        impl AnyControl {
+            /// Wraps an existing control using the wrapper for its class.
+            pub unsafe fn from_handle(ih: *mut Ihandle) -> AnyControl {
+                ::iup_open();
+                let class_name = get_class_name(ih);
+                $(
+                    if $variant::CLASS_NAMES.contains(&&*class_name) {
+                        return AnyControl::$variant($variant::from_handle_unchecked(ih));
+                    }
+                )*
+                AnyControl::Unknown(UnknownControl(HandleRc::new(ih)))
+            }
+
+            fn as_control(&self) -> &Control {
+                match *self {
+                    $(AnyControl::$variant(ref c) => c,)*
+                    AnyControl::Unknown(ref c) => c,
+                }
+            }
+        }
+
+        $(
+            impl From<$variant> for AnyControl {
+                fn from(control: $variant) -> AnyControl {
+                    AnyControl::$variant(control)
+                }
+            }
+        )*
+    };
+}
+
+any_control!(
+    Button,
+    Canvas,
+    Clipboard,
+    Dialog,
+    FileDlg,
+    Fill,
+    Frame,
+    GridBox,
+    Hbox,
+    Image,
+    Item,
+    Label,
+    List,
+    Menu,
+    Radio,
+    Separator,
+    Submenu,
+    Tabs,
+    Text,
+    Timer,
+    Toggle,
+    Vbox
+);
+
+impl AnyControl {
+    /// The name of the control's class, such as "button" or "dialog".
+    pub fn class_name(&self) -> String {
+        get_class_name(self.handle())
+    }
+
+    /// Converts to the wrapper `T`, or returns `self` if the control isn't of that type.
+    pub fn downcast<T: ControlType>(self) -> Result<T, AnyControl> {
+        unsafe {
+            match T::try_from_handle(self.handle()) {
+                Some(control) => Ok(control),
+                None => Err(self),
+            }
+        }
+    }
+}
+
+unsafe impl Control for AnyControl {
+    fn handle(&self) -> *mut Ihandle {
+        self.as_control().handle()
+    }
+}
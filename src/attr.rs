@@ -0,0 +1,282 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+//! Typed access to IUP attributes.
+//!
+//! Every attribute in IUP is a string. An `Attr<T>` pairs an attribute's name with the Rust type
+//! its value converts to, and `AttrValue` does the converting. The descriptors in this module can
+//! be passed to `Control::get` and `Control::set` to use an attribute that doesn't have its own
+//! method yet:
+//!
+//! ```no_run
+//! # use clear_coat::*;
+//! # use clear_coat::attr;
+//! # use clear_coat::common_attrs_cbs::Expand;
+//! let button = Button::new();
+//! button.set(attr::EXPAND, Expand::Horizontal);
+//! let expand = button.get(attr::EXPAND);
+//! ```
+//!
+//! A descriptor for an attribute that isn't in this module can be made with `Attr::new`.
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use iup_sys::Ihandle;
+use super::attributes::{
+    get_parsed_attribute,
+    reset_attribute,
+    set_str_attribute,
+    Cursor,
+    Expand,
+    Orientations,
+};
+use super::error::Error;
+use super::{HAlignment, Orientation, VAlignment};
+
+/// A type that an attribute's string value can be converted to and from.
+pub trait AttrValue : Sized {
+    /// Converts an attribute's value. Returns `None` if the value is not valid for this type.
+    fn parse_attr(s: &str) -> Option<Self>;
+
+    /// Converts to an attribute's value. The string may end in a null terminator, which avoids
+    /// copying it before passing it to IUP.
+    fn format_attr(&self) -> Cow<'static, str>;
+}
+
+/// A typed descriptor of an attribute.
+pub struct Attr<T> {
+    name: &'static str,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Attr<T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for Attr<T> {}
+
+impl<T> Attr<T> {
+    /// Creates a descriptor for the attribute `name`. The name may end in a null terminator,
+    /// which avoids copying it each time the attribute is used.
+    pub const fn new(name: &'static str) -> Attr<T> {
+        Attr { name: name, phantom: PhantomData }
+    }
+
+    /// The name of the attribute, without a null terminator.
+    pub fn name(&self) -> &'static str {
+        self.name.trim_end_matches('\0')
+    }
+
+    pub(crate) fn reset_handle(&self, ih: *mut Ihandle) {
+        reset_attribute(ih, self.name);
+    }
+}
+
+impl<T: AttrValue> Attr<T> {
+    pub(crate) fn try_get_handle(&self, ih: *mut Ihandle) -> Result<T, Error> {
+        get_parsed_attribute(ih, self.name, T::parse_attr)
+    }
+
+    pub(crate) fn get_handle(&self, ih: *mut Ihandle) -> T {
+        self.try_get_handle(ih).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub(crate) fn set_handle(&self, ih: *mut Ihandle, value: &T) {
+        set_str_attribute(ih, self.name, &value.format_attr());
+    }
+}
+
+// Defines a getter, a `try_` getter, and a setter (unless it is read-only) that use an `Attr`.
+// It works in both traits and inherent impls.
+macro_rules! attr_methods {
+    ($(#[$meta:meta])* $vis:vis $getter:ident, $try_getter:ident, $setter:ident: $ty:ty = $attr:expr) => {
+        attr_methods!($(#[$meta])* $vis $getter, $try_getter: $ty = $attr);
+
+        $vis fn $setter(&self, value: $ty) -> &Self {
+            $attr.set_handle(self.handle(), &value);
+            self
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis $getter:ident, $try_getter:ident: $ty:ty = $attr:expr) => {
+        $(#[$meta])*
+        $vis fn $getter(&self) -> $ty {
+            $attr.get_handle(self.handle())
+        }
+
+        /// Returns an error instead of panicking if the attribute's value is invalid.
+        $vis fn $try_getter(&self) -> Result<$ty, $crate::Error> {
+            $attr.try_get_handle(self.handle())
+        }
+    };
+}
+
+impl AttrValue for bool {
+    // This matches what IUP accepts as a boolean, except that other values are invalid instead
+    // of false. An unset attribute is false.
+    fn parse_attr(s: &str) -> Option<Self> {
+        match &*s.to_ascii_uppercase() {
+            "YES" | "ON" | "Y" | "1" => Some(true),
+            "NO" | "OFF" | "N" | "0" | "" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn format_attr(&self) -> Cow<'static, str> {
+        if *self { "YES\0".into() } else { "NO\0".into() }
+    }
+}
+
+impl AttrValue for String {
+    fn parse_attr(s: &str) -> Option<Self> {
+        Some(s.to_owned())
+    }
+
+    fn format_attr(&self) -> Cow<'static, str> {
+        self.clone().into()
+    }
+}
+
+macro_rules! impl_attr_value_from_str {
+    ($($ty:ty),*) => {
+        $(
+            impl AttrValue for $ty {
+                fn parse_attr(s: &str) -> Option<Self> {
+                    s.parse().ok()
+                }
+
+                fn format_attr(&self) -> Cow<'static, str> {
+                    format!("{}\0", self).into()
+                }
+            }
+        )*
+    };
+}
+
+impl_attr_value_from_str!(i32, u32, usize, f32, f64);
+
+// IUP separates the two numbers with an 'x' in sizes and with a ':' or ',' in other attributes.
+fn split_pair(s: &str) -> Option<(&str, &str)> {
+    s.find(|c| c == 'x' || c == ':' || c == ',').map(|i| (&s[..i], &s[i + 1..]))
+}
+
+impl AttrValue for (i32, i32) {
+    fn parse_attr(s: &str) -> Option<Self> {
+        split_pair(s).and_then(|(a, b)| {
+            match (a.parse(), b.parse()) {
+                (Ok(a), Ok(b)) => Some((a, b)),
+                _ => None,
+            }
+        })
+    }
+
+    fn format_attr(&self) -> Cow<'static, str> {
+        format!("{}x{}\0", self.0, self.1).into()
+    }
+}
+
+impl AttrValue for (u32, u32) {
+    fn parse_attr(s: &str) -> Option<Self> {
+        split_pair(s).and_then(|(a, b)| {
+            match (a.parse(), b.parse()) {
+                (Ok(a), Ok(b)) => Some((a, b)),
+                _ => None,
+            }
+        })
+    }
+
+    fn format_attr(&self) -> Cow<'static, str> {
+        format!("{}x{}\0", self.0, self.1).into()
+    }
+}
+
+// Setting an image cursor with this doesn't keep a reference to the image. Use
+// `CursorAttribute::set_cursor` instead.
+impl AttrValue for Cursor {
+    fn parse_attr(s: &str) -> Option<Self> { Cursor::from_str(s) }
+    fn format_attr(&self) -> Cow<'static, str> { self.to_str() }
+}
+
+impl AttrValue for Expand {
+    fn parse_attr(s: &str) -> Option<Self> { Expand::from_str(s) }
+    fn format_attr(&self) -> Cow<'static, str> { self.to_str() }
+}
+
+// This is the format of NORMALIZESIZE. SCROLLBAR uses "YES" instead of "BOTH", so it doesn't
+// use this.
+impl AttrValue for Orientations {
+    fn parse_attr(s: &str) -> Option<Self> { Orientations::from_str(s) }
+    fn format_attr(&self) -> Cow<'static, str> { self.to_str().into() }
+}
+
+impl AttrValue for Orientation {
+    fn parse_attr(s: &str) -> Option<Self> { Orientation::from_str(s.as_bytes()) }
+    fn format_attr(&self) -> Cow<'static, str> { self.to_str().into() }
+}
+
+impl AttrValue for VAlignment {
+    fn parse_attr(s: &str) -> Option<Self> { VAlignment::from_str(s.as_bytes()) }
+    fn format_attr(&self) -> Cow<'static, str> { self.to_str().into() }
+}
+
+impl AttrValue for HAlignment {
+    fn parse_attr(s: &str) -> Option<Self> { HAlignment::from_str(s.as_bytes()) }
+    fn format_attr(&self) -> Cow<'static, str> { self.to_str().into() }
+}
+
+pub const ACTIVE: Attr<bool> = Attr::new("ACTIVE\0");
+pub const CANFOCUS: Attr<bool> = Attr::new("CANFOCUS\0");
+pub const CURSOR: Attr<Cursor> = Attr::new("CURSOR\0");
+pub const EXPAND: Attr<Expand> = Attr::new("EXPAND\0");
+pub const MINSIZE: Attr<(i32, i32)> = Attr::new("MINSIZE\0");
+pub const MAXSIZE: Attr<(i32, i32)> = Attr::new("MAXSIZE\0");
+pub const ORIENTATION: Attr<Orientation> = Attr::new("ORIENTATION\0");
+pub const RASTERSIZE: Attr<(u32, u32)> = Attr::new("RASTERSIZE\0");
+pub const TIP: Attr<String> = Attr::new("TIP\0");
+pub const TITLE: Attr<String> = Attr::new("TITLE\0");
+pub const VISIBLE: Attr<bool> = Attr::new("VISIBLE\0");
+pub const VISIBLECOLUMNS: Attr<u32> = Attr::new("VISIBLECOLUMNS\0");
+pub const VISIBLELINES: Attr<u32> = Attr::new("VISIBLELINES\0");
+
+pub const DX: Attr<f32> = Attr::new("DX\0");
+pub const DY: Attr<f32> = Attr::new("DY\0");
+pub const POSX: Attr<f32> = Attr::new("POSX\0");
+pub const POSY: Attr<f32> = Attr::new("POSY\0");
+pub const XMIN: Attr<f32> = Attr::new("XMIN\0");
+pub const XMAX: Attr<f32> = Attr::new("XMAX\0");
+pub const YMIN: Attr<f32> = Attr::new("YMIN\0");
+pub const YMAX: Attr<f32> = Attr::new("YMAX\0");
+pub const LINEX: Attr<f32> = Attr::new("LINEX\0");
+pub const LINEY: Attr<f32> = Attr::new("LINEY\0");
+pub const DRAWSIZE: Attr<(i32, i32)> = Attr::new("DRAWSIZE\0");
+
+pub const EXPANDCHILDREN: Attr<bool> = Attr::new("EXPANDCHILDREN\0");
+pub const GAP: Attr<u32> = Attr::new("GAP\0");
+pub const NGAP: Attr<u32> = Attr::new("NGAP\0");
+pub const MARGIN: Attr<(u32, u32)> = Attr::new("MARGIN\0");
+pub const NMARGIN: Attr<(u32, u32)> = Attr::new("NMARGIN\0");
+pub const NORMALIZESIZE: Attr<Orientations> = Attr::new("NORMALIZESIZE\0");
+pub const NUMLIN: Attr<u32> = Attr::new("NUMLIN\0");
+pub const NUMCOL: Attr<u32> = Attr::new("NUMCOL\0");
+pub const SIZECOL: Attr<u32> = Attr::new("SIZECOL\0");
+pub const SIZELIN: Attr<u32> = Attr::new("SIZELIN\0");
+pub const GAPLIN: Attr<u32> = Attr::new("GAPLIN\0");
+pub const GAPCOL: Attr<u32> = Attr::new("GAPCOL\0");
+pub const ALIGNMENTLIN: Attr<VAlignment> = Attr::new("ALIGNMENTLIN\0");
+pub const ALIGNMENTCOL: Attr<HAlignment> = Attr::new("ALIGNMENTCOL\0");
+
+pub const APPENDNEWLINE: Attr<bool> = Attr::new("APPENDNEWLINE\0");
+pub const MULTILINE: Attr<bool> = Attr::new("MULTILINE\0");
+pub const DROPDOWN: Attr<bool> = Attr::new("DROPDOWN\0");
+pub const EDITBOX: Attr<bool> = Attr::new("EDITBOX\0");
+pub const MULTIPLE: Attr<bool> = Attr::new("MULTIPLE\0");
+pub const VALUEPOS: Attr<usize> = Attr::new("VALUEPOS\0");
+pub const COUNT: Attr<usize> = Attr::new("COUNT\0");
+pub const THREESTATE: Attr<bool> = Attr::new("3STATE\0");
+pub const TIME: Attr<u32> = Attr::new("TIME\0");
+pub const MULTIPLEFILES: Attr<bool> = Attr::new("MULTIPLEFILES\0");
+pub const WIDTH: Attr<u32> = Attr::new("WIDTH\0");
+pub const HEIGHT: Attr<u32> = Attr::new("HEIGHT\0");
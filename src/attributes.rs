@@ -9,10 +9,17 @@ use std::borrow::Cow;
 use std::cell::Cell;
 use std::ffi::CStr;
 use libc::{c_char, c_int};
+#[cfg(unix)]
+use libc::c_long;
 use iup_sys::*;
 use smallvec::SmallVec;
+#[cfg(windows)]
 use winapi;
-use super::Control;
+use super::{Control, ControlType};
+use super::attr;
+use super::error::Error;
+use super::extra_refs::ExtraRefKey;
+use super::image::{get_image_attribute, replace_image_ref, set_image_attribute, Image};
 
 pub fn str_to_c_vec<'a: 'b, 'b, A: ::smallvec::Array<Item=u8>>(s: &'a str, buf: &'b mut SmallVec<A>) -> *const c_char {
     // `CString` in the std library doesn't check if the &str already ends in a null terminator
@@ -103,7 +110,6 @@ pub unsafe fn get_str_attribute_slice(handle: *mut Ihandle, name: &str) -> Cow<s
     }
 }
 
-#[cfg(for_future_use)] // silence dead_code warning (probably) the best way
 pub fn get_attribute_handle(ih: *mut Ihandle, name: &str) -> *mut Ihandle {
     unsafe {
         let mut name_buf = SmallVec::<[u8; 64]>::new();
@@ -112,17 +118,34 @@ pub fn get_attribute_handle(ih: *mut Ihandle, name: &str) -> *mut Ihandle {
     }
 }
 
+// Gets an attribute and converts it using `parse`. If `parse` returns `None`, the error has the
+// attribute's name and value.
+pub fn get_parsed_attribute<T, F>(handle: *mut Ihandle, name: &str, parse: F) -> Result<T, Error>
+where F: FnOnce(&str) -> Option<T> {
+    unsafe {
+        let s = get_str_attribute_slice(handle, name);
+        parse(&s).ok_or_else(|| Error::invalid_attribute(name, &s))
+    }
+}
+
 pub fn get_int_int_attribute(handle: *mut Ihandle, name: &str) -> (i32, i32) {
+    try_get_int_int_attribute(handle, name).expect("could not convert attribute to two integers")
+}
+
+pub fn try_get_int_int_attribute(handle: *mut Ihandle, name: &str) -> Result<(i32, i32), Error> {
     unsafe {
         let mut name_buf = SmallVec::<[u8; 64]>::new();
         let c_name = str_to_c_vec(name, &mut name_buf);
         let mut x: i32 = 0;
         let mut y: i32 = 0;
-        assert!(IupGetIntInt(handle as *mut Ihandle,
-                             c_name,
-                             &mut x as *mut c_int,
-                             &mut y as *mut c_int) == 2);
-        (x, y)
+        if IupGetIntInt(handle as *mut Ihandle,
+                        c_name,
+                        &mut x as *mut c_int,
+                        &mut y as *mut c_int) == 2 {
+            Ok((x, y))
+        } else {
+            Err(Error::invalid_attribute(name, &get_str_attribute_slice(handle, name)))
+        }
     }
 }
 
@@ -146,13 +169,13 @@ pub enum Orientations {
 }
 
 impl Orientations {
-    pub(crate) fn from_str(s: &str) -> Self {
+    pub(crate) fn from_str(s: &str) -> Option<Self> {
         match s {
-            "VERTICAL" => Orientations::Vertical,
-            "HORIZONTAL" => Orientations::Horizontal,
-            "BOTH" => Orientations::Both,
-            "NO" => Orientations::None,
-            _ => panic!("unknown Orientations"),
+            "VERTICAL" => Some(Orientations::Vertical),
+            "HORIZONTAL" => Some(Orientations::Horizontal),
+            "BOTH" => Some(Orientations::Both),
+            "NO" => Some(Orientations::None),
+            _ => None,
         }
     }
 
@@ -167,13 +190,7 @@ impl Orientations {
 }
 
 pub trait ActiveAttribute : Control {
-    fn active(&self) -> bool {
-        get_str_attribute(self.handle(), "ACTIVE\0") == "YES"
-    }
-
-    fn set_active(&self, active: bool) {
-        set_str_attribute(self.handle(), "ACTIVE\0", if active { "YES\0" } else { "NO\0" });
-    }
+    attr_methods!(active, try_active, set_active: bool = attr::ACTIVE);
 }
 
 pub trait CanvasAttributes : Control {
@@ -187,143 +204,25 @@ pub trait CanvasAttributes : Control {
         get_attribute_ptr(self.handle(), "HWND\0") as winapi::HDC
     }
 
-    fn dx(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "DX\0");
-            s.parse().expect("could not convert DX to a number")
-        }
-    }
-
-    fn set_dx(&self, dx: f32) -> &Self {
-        set_str_attribute(self.handle(), "DX\0", &format!("{}\0", dx));
-        self
-    }
-
-    fn dy(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "DY\0");
-            s.parse().expect("could not convert DY to a number")
-        }
-    }
-
-    fn set_dy(&self, dy: f32) -> &Self {
-        set_str_attribute(self.handle(), "DY\0", &format!("{}\0", dy));
-        self
-    }
-
-    fn pos_x(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "POSX\0");
-            s.parse().expect("could not convert POSX to a number")
-        }
-    }
-
-    fn set_pos_x(&self, pos_x: f32) -> &Self {
-        set_str_attribute(self.handle(), "POSX\0", &format!("{}\0", pos_x));
-        self
-    }
-
-    fn pos_y(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "POSY\0");
-            s.parse().expect("could not convert POSY to a number")
-        }
-    }
-
-    fn set_pos_y(&self, pos_y: f32) -> &Self {
-        set_str_attribute(self.handle(), "POSY\0", &format!("{}\0", pos_y));
-        self
-    }
-
-    fn x_min(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "XMIN\0");
-            s.parse().expect("could not convert XMIN to a number")
-        }
-    }
-
-    fn set_x_min(&self, x_min: f32) -> &Self {
-        set_str_attribute(self.handle(), "XMIN\0", &format!("{}\0", x_min));
-        self
-    }
-
-    fn x_max(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "XMAX\0");
-            s.parse().expect("could not convert XMAX to a number")
-        }
-    }
-
-    fn set_x_max(&self, x_max: f32) -> &Self {
-        set_str_attribute(self.handle(), "XMAX\0", &format!("{}\0", x_max));
-        self
-    }
-
-    fn y_min(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "YMIN\0");
-            s.parse().expect("could not convert YMIN to a number")
-        }
-    }
-
-    fn set_y_min(&self, y_min: f32) -> &Self {
-        set_str_attribute(self.handle(), "YMIN\0", &format!("{}\0", y_min));
-        self
-    }
-
-    fn y_max(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "YMAX\0");
-            s.parse().expect("could not convert YMAX to a number")
-        }
-    }
-
-    fn set_y_max(&self, y_max: f32) -> &Self {
-        set_str_attribute(self.handle(), "YMAX\0", &format!("{}\0", y_max));
-        self
-    }
-
-    fn line_x(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "LINEX\0");
-            s.parse().expect("could not convert LINEX to a number")
-        }
-    }
-
-    fn set_line_x(&self, line_x: f32) -> &Self {
-        set_str_attribute(self.handle(), "LINEX\0", &format!("{}\0", line_x));
-        self
-    }
-
-    fn line_y(&self) -> f32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "LINEY\0");
-            s.parse().expect("could not convert LINEY to a number")
-        }
-    }
-
-    fn set_line_y(&self, line_y: f32) -> &Self {
-        set_str_attribute(self.handle(), "LINEY\0", &format!("{}\0", line_y));
-        self
-    }
+    attr_methods!(dx, try_dx, set_dx: f32 = attr::DX);
+    attr_methods!(dy, try_dy, set_dy: f32 = attr::DY);
+    attr_methods!(pos_x, try_pos_x, set_pos_x: f32 = attr::POSX);
+    attr_methods!(pos_y, try_pos_y, set_pos_y: f32 = attr::POSY);
+    attr_methods!(x_min, try_x_min, set_x_min: f32 = attr::XMIN);
+    attr_methods!(x_max, try_x_max, set_x_max: f32 = attr::XMAX);
+    attr_methods!(y_min, try_y_min, set_y_min: f32 = attr::YMIN);
+    attr_methods!(y_max, try_y_max, set_y_max: f32 = attr::YMAX);
+    attr_methods!(line_x, try_line_x, set_line_x: f32 = attr::LINEX);
+    attr_methods!(line_y, try_line_y, set_line_y: f32 = attr::LINEY);
 
     // TODO: XAUTOHIDE, YAUTOHIDE, XHIDDEN, YHIDDEN, and others for ScrollbarAttribute
 }
 
 pub trait CanFocusAttribute : Control {
-    fn can_focus(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "CANFOCUS\0") == "YES"
-        }
-    }
-
-    fn set_can_focus(&self, can_focus: bool) -> &Self {
-        set_str_attribute(self.handle(), "CANFOCUS\0", if can_focus { "YES\0" } else { "NO\0" });
-        self
-    }
+    attr_methods!(can_focus, try_can_focus, set_can_focus: bool = attr::CANFOCUS);
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum Cursor {
     // Loading cursors from application resources is purposefully not supported, as doing that
     // is more platform-specific and is unnecessary when you can create and use an `Image`.
@@ -351,45 +250,46 @@ pub enum Cursor {
     /// Windows only
     No,
     UpArrow,
-    // TODO: once images are wrapped, be able to pass one into set_cursor
-    // set_cursor would assign it a random name, and assign that name to the CURSOR attribute
-    // It also must add_extra_ref to the image
-    //Image(&Image),
-    Image,
+    /// A custom cursor. The image's hotspot is the point that is the mouse position. Use
+    /// `CursorAttribute::set_cursor` to set it, which keeps a reference to the image.
+    Image(Image),
 }
 
 impl Cursor {
-    fn from_str(s: &str) -> Self {
+    pub(crate) fn from_str(s: &str) -> Option<Self> {
         match s {
-            "NONE" => Cursor::None,
-            "ARROW" => Cursor::Arrow,
-            "BUSY" => Cursor::Busy,
-            "CROSS" => Cursor::Cross,
-            "HAND" => Cursor::Hand,
-            "HELP" => Cursor::Help,
-            "MOVE" => Cursor::Move,
-            "RESIZE_N" => Cursor::ResizeN,
-            "RESIZE_S" => Cursor::ResizeS,
-            "RESIZE_NS" => Cursor::ResizeNS,
-            "RESIZE_W" => Cursor::ResizeW,
-            "RESIZE_E" => Cursor::ResizeE,
-            "RESIZE_WE" => Cursor::ResizeWE,
-            "RESIZE_NE" => Cursor::ResizeNE,
-            "RESIZE_SW" => Cursor::ResizeSW,
-            "RESIZE_NW" => Cursor::ResizeNW,
-            "RESIZE_SE" => Cursor::ResizeSE,
-            "TEXT" => Cursor::Text,
-            "APPSTARTING" => Cursor::AppStarting,
-            "NO" => Cursor::No,
-            "UPARROW" => Cursor::UpArrow,
-            _ => {
-                unimplemented!(); // TODO: Image
+            "NONE" => Some(Cursor::None),
+            "ARROW" => Some(Cursor::Arrow),
+            "BUSY" => Some(Cursor::Busy),
+            "CROSS" => Some(Cursor::Cross),
+            "HAND" => Some(Cursor::Hand),
+            "HELP" => Some(Cursor::Help),
+            "MOVE" => Some(Cursor::Move),
+            "RESIZE_N" => Some(Cursor::ResizeN),
+            "RESIZE_S" => Some(Cursor::ResizeS),
+            "RESIZE_NS" => Some(Cursor::ResizeNS),
+            "RESIZE_W" => Some(Cursor::ResizeW),
+            "RESIZE_E" => Some(Cursor::ResizeE),
+            "RESIZE_WE" => Some(Cursor::ResizeWE),
+            "RESIZE_NE" => Some(Cursor::ResizeNE),
+            "RESIZE_SW" => Some(Cursor::ResizeSW),
+            "RESIZE_NW" => Some(Cursor::ResizeNW),
+            "RESIZE_SE" => Some(Cursor::ResizeSE),
+            "TEXT" => Some(Cursor::Text),
+            "APPSTARTING" => Some(Cursor::AppStarting),
+            "NO" => Some(Cursor::No),
+            "UPARROW" => Some(Cursor::UpArrow),
+            // Any other value is the name of an image.
+            name => unsafe {
+                let mut name_buf = SmallVec::<[u8; 64]>::new();
+                let ih = IupGetHandle(str_to_c_vec(name, &mut name_buf));
+                if ih.is_null() { None } else { Image::try_from_handle(ih).map(Cursor::Image) }
             },
         }
     }
 
-    fn to_str(self) -> Cow<'static, str> {
-        match self {
+    pub(crate) fn to_str(&self) -> Cow<'static, str> {
+        match *self {
             Cursor::None => "NONE\0".into(),
             Cursor::Arrow => "ARROW\0".into(),
             Cursor::Busy => "BUSY\0".into(),
@@ -411,9 +311,9 @@ impl Cursor {
             Cursor::AppStarting => "APPSTARTING\0".into(),
             Cursor::No => "NO\0".into(),
             Cursor::UpArrow => "UPARROW\0".into(),
-            Cursor::Image => {
+            Cursor::Image(ref image) => {
                 unsafe {
-                    let img: *mut Ihandle = ::std::ptr::null_mut(); // TODO:
+                    let img = image.handle();
                     let curr_name = IupGetName(img);
                     if !curr_name.is_null() {
                         CStr::from_ptr(curr_name).to_string_lossy().into_owned().into()
@@ -428,22 +328,23 @@ impl Cursor {
     }
 }
 
+const EXTRA_REF_CURSOR: ExtraRefKey = ExtraRefKey(6);
+
 pub trait CursorAttribute : Control {
-    fn cursor(&self) -> Cursor {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "CURSOR\0");
-            Cursor::from_str(&s)
-        }
-    }
+    attr_methods!(cursor, try_cursor: Cursor = attr::CURSOR);
 
     fn set_cursor(&self, cursor: Cursor) -> &Self {
-        let s = cursor.to_str();
-        set_str_attribute(self.handle(), "CURSOR\0", &s);
+        attr::CURSOR.set_handle(self.handle(), &cursor);
+        let image = match cursor {
+            Cursor::Image(ref image) => Some(image),
+            _ => None,
+        };
+        replace_image_ref(self.handle(), EXTRA_REF_CURSOR, image);
         self
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Expand {
     Yes,
     Horizontal,
@@ -454,19 +355,19 @@ pub enum Expand {
 }
 
 impl Expand {
-    fn from_str(s: &str) -> Self {
+    pub(crate) fn from_str(s: &str) -> Option<Self> {
         match s {
-            "YES" => Expand::Yes,
-            "HORIZONTAL" => Expand::Horizontal,
-            "VERTICAL" => Expand::Vertical,
-            "HORIZONTALFREE" => Expand::HorizontalFree,
-            "VERTICALFREE" => Expand::VerticalFree,
-            "NO" => Expand::No,
-            _ => panic!("unknown Expand"),
+            "YES" => Some(Expand::Yes),
+            "HORIZONTAL" => Some(Expand::Horizontal),
+            "VERTICAL" => Some(Expand::Vertical),
+            "HORIZONTALFREE" => Some(Expand::HorizontalFree),
+            "VERTICALFREE" => Some(Expand::VerticalFree),
+            "NO" => Some(Expand::No),
+            _ => None,
         }
     }
 
-    fn to_str(self) -> Cow<'static, str> {
+    pub(crate) fn to_str(self) -> Cow<'static, str> {
         match self {
             Expand::Yes => "YES\0".into(),
             Expand::Horizontal => "HORIZONTAL\0".into(),
@@ -479,24 +380,11 @@ impl Expand {
 }
 
 pub trait ExpandAttribute : Control {
-    fn expand(&self) -> Expand {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "EXPAND\0");
-            Expand::from_str(&s)
-        }
-    }
-
-    fn set_expand(&self, expand: Expand) -> &Self {
-        let s = expand.to_str();
-        set_str_attribute(self.handle(), "EXPAND\0", &s);
-        self
-    }
+    attr_methods!(expand, try_expand, set_expand: Expand = attr::EXPAND);
 }
 
 pub trait MinMaxSizeAttribute : Control {
-    fn min_size(&self) -> (i32, i32) {
-        get_int_int_attribute(self.handle(), "MINSIZE\0")
-    }
+    attr_methods!(min_size, try_min_size: (i32, i32) = attr::MINSIZE);
 
     fn set_min_size(&self, x: i32, y: i32) -> &Self {
         let s = format!("{}x{}\0", x, y);
@@ -504,9 +392,7 @@ pub trait MinMaxSizeAttribute : Control {
         self
     }
 
-    fn max_size(&self) -> (i32, i32) {
-        get_int_int_attribute(self.handle(), "MAXSIZE\0")
-    }
+    attr_methods!(max_size, try_max_size: (i32, i32) = attr::MAXSIZE);
 
     fn set_max_size(&self, x: i32, y: i32) -> &Self {
         let s = format!("{}x{}\0", x, y);
@@ -516,27 +402,17 @@ pub trait MinMaxSizeAttribute : Control {
 }
 
 pub trait OrientationAttribute : Control {
-    fn orientation(&self) -> ::Orientation {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "ORIENTATION\0");
-            ::Orientation::from_str(s.as_bytes())
-        }
-    }
-
-    fn set_orientation(&self, orientation: ::Orientation) -> &Self {
-        set_str_attribute(self.handle(), "ORIENTATION\0", orientation.to_str());
-        self
-    }
+    attr_methods!(orientation, try_orientation, set_orientation: ::Orientation = attr::ORIENTATION);
 }
 
 impl Orientations {
-    fn from_scrollbar_str(s: &str) -> Self {
+    fn from_scrollbar_str(s: &str) -> Option<Self> {
         match s {
-            "VERTICAL" => Orientations::Vertical,
-            "HORIZONTAL" => Orientations::Horizontal,
-            "YES" => Orientations::Both,
-            "NO" => Orientations::None,
-            _ => panic!("unknown scrollbar Orientations"),
+            "VERTICAL" => Some(Orientations::Vertical),
+            "HORIZONTAL" => Some(Orientations::Horizontal),
+            "YES" => Some(Orientations::Both),
+            "NO" => Some(Orientations::None),
+            _ => None,
         }
     }
 
@@ -552,10 +428,11 @@ impl Orientations {
 
 pub trait ScrollbarAttribute : Control {
     fn scrollbar(&self) -> Orientations {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "SCROLLBAR\0");
-            Orientations::from_scrollbar_str(&s)
-        }
+        self.try_scrollbar().expect("unknown scrollbar Orientations")
+    }
+
+    fn try_scrollbar(&self) -> Result<Orientations, Error> {
+        get_parsed_attribute(self.handle(), "SCROLLBAR\0", Orientations::from_scrollbar_str)
     }
 
     fn set_scrollbar(&self, orientations: Orientations) -> &Self {
@@ -565,10 +442,7 @@ pub trait ScrollbarAttribute : Control {
 }
 
 pub trait SizeAttribute : Control {
-    fn raster_size(&self) -> (u32, u32) {
-        let (w, h) = get_int_int_attribute(self.handle(), "RASTERSIZE\0");
-        (w as u32, h as u32)
-    }
+    attr_methods!(raster_size, try_raster_size: (u32, u32) = attr::RASTERSIZE);
 
     fn set_raster_size(&self, width: u32, height: u32) -> &Self {
         let s = format!("{}x{}\0", width, height);
@@ -578,10 +452,7 @@ pub trait SizeAttribute : Control {
 }
 
 pub trait SingleSizeAttribute : Control {
-    fn raster_size(&self) -> (u32, u32) {
-        let (w, h) = get_int_int_attribute(self.handle(), "RASTERSIZE\0");
-        (w as u32, h as u32)
-    }
+    attr_methods!(raster_size, try_raster_size: (u32, u32) = attr::RASTERSIZE);
 
     fn set_raster_size(&self, size: u32) -> &Self {
         set_str_attribute(self.handle(), "RASTERSIZE\0", &format!("{}\0", size));
@@ -614,60 +485,70 @@ pub trait TitleAttribute : Control {
     }
 }
 
-pub trait VisibleAttribute : Control {
-    fn show(&self) -> Result<(), ()> {
-        unsafe {
-            if IupShow(self.handle()) == IUP_NOERROR {
-                Ok(())
-            } else {
-                Err(())
-            }
-        }
+const EXTRA_REF_IMAGE: ExtraRefKey = ExtraRefKey(2);
+const EXTRA_REF_IMPRESS: ExtraRefKey = ExtraRefKey(3);
+const EXTRA_REF_IMINACTIVE: ExtraRefKey = ExtraRefKey(4);
+
+pub trait ImageAttribute : Control {
+    fn image(&self) -> Option<Image> {
+        get_image_attribute(self.handle(), "IMAGE\0")
     }
 
-    fn hide(&self) -> Result<(), ()> {
-        unsafe {
-            if IupHide(self.handle()) == IUP_NOERROR {
-                Ok(())
-            } else {
-                Err(())
-            }
-        }
+    /// Shows an image on the control. `None` removes it. The control keeps a reference to the
+    /// image.
+    fn set_image(&self, image: Option<&Image>) -> &Self {
+        set_image_attribute(self.handle(), "IMAGE\0", EXTRA_REF_IMAGE, image);
+        self
     }
+}
 
-    fn visible(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "VISIBLE\0") == "YES"
-        }
+pub trait PressedImageAttribute : Control {
+    fn pressed_image(&self) -> Option<Image> {
+        get_image_attribute(self.handle(), "IMPRESS\0")
     }
 
-    fn set_visible(&self, visible: bool) -> Result<(), ()> {
-        if visible { self.show() } else { self.hide() }
+    /// Sets the image shown while the control is pressed, or for a menu item, while it is
+    /// checked. `None` uses the normal image.
+    fn set_pressed_image(&self, image: Option<&Image>) -> &Self {
+        set_image_attribute(self.handle(), "IMPRESS\0", EXTRA_REF_IMPRESS, image);
+        self
     }
 }
 
-pub trait VisibleColumnsLinesAttribute : Control {
-    fn visible_columns(&self) -> u32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "VISIBLECOLUMNS\0");
-            s.parse().expect("could not convert VISIBLECOLUMNS to an integer")
-        }
+pub trait InactiveImageAttribute : Control {
+    fn inactive_image(&self) -> Option<Image> {
+        get_image_attribute(self.handle(), "IMINACTIVE\0")
     }
 
-    fn set_visible_columns(&self, cols: u32) -> &Self {
-        set_str_attribute(self.handle(), "VISIBLECOLUMNS\0", &format!("{}\0", cols));
+    /// Sets the image shown while the control is inactive. `None` uses a grayed out version of
+    /// the normal image.
+    fn set_inactive_image(&self, image: Option<&Image>) -> &Self {
+        set_image_attribute(self.handle(), "IMINACTIVE\0", EXTRA_REF_IMINACTIVE, image);
         self
     }
+}
 
-    fn visible_lines(&self) -> u32 {
+pub trait VisibleAttribute : Control {
+    fn show(&self) -> Result<(), Error> {
         unsafe {
-            let s = get_str_attribute_slice(self.handle(), "VISIBLELINES\0");
-            s.parse().expect("could not convert VISIBLELINES to an integer")
+            Error::check("IupShow", IupShow(self.handle()))
         }
     }
 
-    fn set_visible_lines(&self, lines: u32) -> &Self {
-        set_str_attribute(self.handle(), "VISIBLELINES\0", &format!("{}\0", lines));
-        self
+    fn hide(&self) -> Result<(), Error> {
+        unsafe {
+            Error::check("IupHide", IupHide(self.handle()))
+        }
+    }
+
+    attr_methods!(visible, try_visible: bool = attr::VISIBLE);
+
+    fn set_visible(&self, visible: bool) -> Result<(), Error> {
+        if visible { self.show() } else { self.hide() }
     }
 }
+
+pub trait VisibleColumnsLinesAttribute : Control {
+    attr_methods!(visible_columns, try_visible_columns, set_visible_columns: u32 = attr::VISIBLECOLUMNS);
+    attr_methods!(visible_lines, try_visible_lines, set_visible_lines: u32 = attr::VISIBLELINES);
+}
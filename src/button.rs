@@ -5,7 +5,12 @@
  * modified, or distributed except according to those terms.
  */
 
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
 use super::control_prelude::*;
+use super::executor::Signal;
 
 #[derive(Clone)]
 pub struct Button(HandleRc);
@@ -41,20 +46,69 @@ impl Button {
     where &'a Self: CoerceUnsized<&'a Control> {
         Event::new(self as &'a Control, &BUTTON_ACTION_CALLBACKS)
     }
+
+    /// Returns a future that completes the next time this button is clicked. See the `executor`
+    /// module.
+    pub fn clicked(&self) -> ButtonClicked {
+        let signal = Signal::new();
+        let signal2 = signal.clone();
+        let token = self.action_event().add(move || signal2.fire());
+        ButtonClicked { button: self.clone(), token: Some(token), signal: signal }
+    }
+}
+
+/// A future that completes when a button is clicked. See `Button::clicked`.
+pub struct ButtonClicked {
+    button: Button,
+    token: Option<ButtonActionCallbackToken>,
+    signal: Rc<Signal>,
+}
+
+impl ButtonClicked {
+    fn remove_handler(&mut self) {
+        // The handler was already removed if the button was destroyed.
+        if let Some(token) = self.token.take() {
+            if !self.button.0.get().is_null() {
+                self.button.action_event().remove(token);
+            }
+        }
+    }
+}
+
+impl Future for ButtonClicked {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let poll = self.signal.poll(cx);
+        if poll.is_ready() {
+            self.get_mut().remove_handler();
+        }
+        poll
+    }
+}
+
+impl Drop for ButtonClicked {
+    fn drop(&mut self) {
+        self.remove_handler();
+    }
 }
 
-impl_control_traits!(Button);
+impl_control_traits!(Button, "button");
 
 impl ActiveAttribute for Button {}
 impl CanFocusAttribute for Button {}
 impl ExpandAttribute for Button {}
+impl ImageAttribute for Button {}
+impl InactiveImageAttribute for Button {}
 impl MinMaxSizeAttribute for Button {}
+impl PressedImageAttribute for Button {}
 impl TipAttribute for Button {}
 impl TitleAttribute for Button {}
 impl VisibleAttribute for Button {}
 
 impl MenuCommonCallbacks for Button {}
 impl GetKillFocusCallbacks for Button {}
+impl KAnyCallback for Button {}
 impl EnterLeaveWindowCallbacks for Button {}
 
 impl ButtonCallback for Button {}
@@ -6,11 +6,13 @@
  */
 
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{hash_map, HashMap};
+use std::ffi::CStr;
 use std::marker::PhantomData;
 use std::ops::{CoerceUnsized};
 use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::thread::LocalKey;
 use libc::{c_int, c_char, c_float};
@@ -21,9 +23,10 @@ use winapi;
 use super::attributes::{
     str_to_c_vec,
     get_str_attribute_slice,
-    get_attribute_ptr,
 };
-use super::{Control, MouseButton, KeyboardMouseStatus};
+#[cfg(windows)]
+use super::attributes::get_attribute_ptr;
+use super::{Control, Key, MouseButton, KeyboardMouseStatus};
 use super::handle_rc::{add_ldestroy_callback, remove_ldestroy_callback};
 
 pub enum CallbackAction {
@@ -62,10 +65,76 @@ thread_local!(
     static PANIC_PAYLOAD: RefCell<Option<Box<Any + Send + 'static>>> = RefCell::new(None)
 );
 
+// If a second callback panics before the first panic is propagated, the first one is kept.
 pub fn set_panic_payload(payload: Box<Any + Send + 'static>) {
-    PANIC_PAYLOAD.with(|cell|
-        *cell.borrow_mut() = Some(payload)
-    );
+    PANIC_PAYLOAD.with(|cell| {
+        let mut pending = cell.borrow_mut();
+        if pending.is_none() {
+            *pending = Some(payload);
+        }
+    });
+}
+
+thread_local!(
+    static PANIC_HOOK: RefCell<Option<Box<FnMut(&(Any + Send)) -> bool>>> = RefCell::new(None)
+);
+
+/// Sets a function that is called with the payload of each panic caught in a callback.
+///
+/// If the function returns true, the panic is considered handled and the main loop keeps
+/// running. If it returns false, the main loop exits and the panic is propagated out of
+/// `main_loop` as if there were no hook. The hook can be used to log panics either way.
+///
+/// If the hook panics, that panic is propagated instead.
+pub fn set_callback_panic_hook<F>(hook: F) where F: FnMut(&(Any + Send)) -> bool + 'static {
+    PANIC_HOOK.with(|cell| *cell.borrow_mut() = Some(Box::new(hook)));
+}
+
+/// Removes the function set with `set_callback_panic_hook`.
+pub fn clear_callback_panic_hook() {
+    PANIC_HOOK.with(|cell| *cell.borrow_mut() = None);
+}
+
+// Set while a panic the hook has already seen is unwinding out of a nested loop, so that the
+// callback that started the loop doesn't pass it to the hook again.
+thread_local!(static RESUMING_PANIC: Cell<bool> = Cell::new(false));
+
+// Continues unwinding a panic that ended a nested loop.
+pub fn resume_panic(payload: Box<Any + Send + 'static>) -> ! {
+    if ::main_loop_level() > 0 {
+        RESUMING_PANIC.with(|cell| cell.set(true));
+    }
+    panic::resume_unwind(payload)
+}
+
+// Called with the payload of a panic caught in a callback. Unless the hook handles it, it is
+// stored to be propagated, and the loop is exited to get to where it can be.
+pub fn handle_callback_panic(payload: Box<Any + Send + 'static>) {
+    // The hook is taken out while it runs so that it can set or clear the hook.
+    let hook = if RESUMING_PANIC.with(|cell| cell.replace(false)) {
+        None
+    } else {
+        PANIC_HOOK.with(|cell| cell.borrow_mut().take())
+    };
+    let payload = match hook {
+        Some(mut hook) => {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| hook(&*payload)));
+            PANIC_HOOK.with(|cell| {
+                let mut current = cell.borrow_mut();
+                if current.is_none() {
+                    *current = Some(hook);
+                }
+            });
+            match result {
+                Ok(true) => return,
+                Ok(false) => payload,
+                Err(hook_payload) => hook_payload,
+            }
+        },
+        None => payload,
+    };
+    set_panic_payload(payload);
+    unsafe { IupExitLoop(); }
 }
 
 pub fn is_panic_pending() -> bool {
@@ -119,6 +188,9 @@ struct ControlCallbacks<F: ?Sized + 'static> {
 pub struct CallbackRegistry<F: ?Sized + 'static, T: Into<Token> + From<Token>> {
     cb_name: &'static str,
     cb_fn: Icallback,
+    // Called when a control gets its first callback, for callbacks that IUP only calls if an
+    // attribute is also set.
+    first_added: Option<fn(*mut Ihandle)>,
     callbacks: Rc<RefCell<HashMap<*mut Ihandle, ControlCallbacks<F>>>>,
     phantom: PhantomData<*const T>,
 }
@@ -130,16 +202,23 @@ impl<F: ?Sized, T: Into<Token> + From<Token>> CallbackRegistry<F, T> {
         CallbackRegistry {
             cb_name: cb_name,
             cb_fn: cb_fn,
+            first_added: None,
             callbacks: Rc::new(RefCell::new(HashMap::new())),
             phantom: PhantomData,
         }
     }
 
+    pub fn with_first_added(mut self, first_added: fn(*mut Ihandle)) -> CallbackRegistry<F, T> {
+        self.first_added = Some(first_added);
+        self
+    }
+
     // `add_callback` and `remove_callback` do not try to borrow the `RefCell` until they have
     // called `Rc::make_mut` (the `RefCell` could already be borrowed by `with_callbacks`). After
     // `Rc::make_mut`, it is guaranteed safe to borrow.
     fn add_callback_inner(&self, ih: *mut Ihandle, cb: Rc<RefCell<F>>) -> T {
         let mut map = self.callbacks.borrow_mut();
+        let first = !map.contains_key(&ih);
         let cc = map.entry(ih).or_insert_with(|| {
             let callbacks2 = self.callbacks.clone();
             let t = add_ldestroy_callback(ih, move |ih| { callbacks2.borrow_mut().remove(&ih); });
@@ -153,6 +232,9 @@ impl<F: ?Sized, T: Into<Token> + From<Token>> CallbackRegistry<F, T> {
             let mut buf = SmallVec::<[u8; 64]>::new();
             IupSetCallback(ih, str_to_c_vec(self.cb_name, &mut buf) as *const i8, self.cb_fn);
         }
+        if let (true, Some(first_added)) = (first, self.first_added) {
+            first_added(ih);
+        }
 
         Token { id: id, ih: ih }.into()
     }
@@ -163,6 +245,16 @@ impl<F: ?Sized, T: Into<Token> + From<Token>> CallbackRegistry<F, T> {
         self.add_callback_inner(ih, Rc::new(RefCell::new(cb)) as Rc<RefCell<F>>)
     }
 
+    // Returns whether any callbacks are added for the control.
+    pub fn has_callbacks(&self, ih: *mut Ihandle) -> bool {
+        self.callbacks.borrow().contains_key(&ih)
+    }
+
+    // Returns whether the callback with the ID is still added for the control.
+    pub fn has_callback(&self, ih: *mut Ihandle, id: usize) -> bool {
+        self.callbacks.borrow().get(&ih).map_or(false, |cc| cc.vec.iter().any(|&(i, _)| i == id))
+    }
+
     pub fn remove_callback(&self, ih: *mut Ihandle, token: T) {
         let token: Token = token.into();
         assert!(ih == token.ih, "token used with wrong control");
@@ -220,8 +312,7 @@ pub fn with_callbacks<F, G: ?Sized, T>(ih: *mut Ihandle,
     match result {
         Ok(r) => r,
         Err(err) => {
-            set_panic_payload(err);
-            unsafe { IupExitLoop(); }
+            handle_callback_panic(err);
             IUP_DEFAULT
         },
     }
@@ -257,6 +348,58 @@ impl<'a, F: ?Sized, T: Into<Token> + From<Token>> Event<'a, F, T> {
     pub fn remove(&self, token: T) {
         self.reg.with(|reg| reg.remove_callback(self.control.handle(), token))
     }
+
+    /// Adds a callback that is removed when the returned guard is dropped, instead of having to
+    /// pass a token to `remove`. If the control has already been destroyed by then, dropping the
+    /// guard does nothing.
+    pub fn add_scoped<G>(&self, cb: G) -> CallbackGuard
+    where Rc<RefCell<G>>: CoerceUnsized<Rc<RefCell<F>>>
+    {
+        let reg = self.reg;
+        let token: Token = self.add(cb).into();
+        let ih = token.ih;
+        // The registry's callbacks are removed when the control is destroyed, but the handle
+        // could be reused by a new control, so the guard needs to know not to remove anything.
+        let destroyed = Rc::new(Cell::new(false));
+        let destroyed2 = destroyed.clone();
+        let ldestroy_token = add_ldestroy_callback(ih, move |_| destroyed2.set(true));
+        CallbackGuard {
+            destroyed: destroyed,
+            remove: Some(Box::new(move || {
+                remove_ldestroy_callback(ldestroy_token);
+                reg.with(|reg| {
+                    if reg.has_callback(ih, token.id) {
+                        reg.remove_callback(ih, token.into());
+                    }
+                });
+            })),
+        }
+    }
+}
+
+/// Removes a callback when dropped. Returned by `Event::add_scoped`.
+#[must_use = "the callback is removed as soon as the guard is dropped"]
+pub struct CallbackGuard {
+    destroyed: Rc<Cell<bool>>,
+    remove: Option<Box<FnOnce()>>,
+}
+
+impl CallbackGuard {
+    /// Drops the guard without removing the callback, so that it stays added until the control
+    /// is destroyed.
+    pub fn forget(mut self) {
+        self.remove = None;
+    }
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        if let Some(remove) = self.remove.take() {
+            if !self.destroyed.get() {
+                remove();
+            }
+        }
+    }
 }
 
 // This macro is only for use by `impl_callbacks!`.
@@ -433,8 +576,27 @@ impl_callbacks! {
     }
 }
 
-pub trait KAnyCallback : Control {
-    // fn k_any_event();
+impl_callbacks! {
+    trait KAnyCallback {
+        "K_ANY\0" => k_any_event {
+            K_ANY_CALLBACKS<FnMut(&Key) -> CallbackAction, KAnyCallbackToken>
+        }
+        unsafe extern fn k_any_cb(ih: *mut Ihandle, c: c_int) -> c_int {
+            // Returning `Ignore` stops the key from being processed, and `Continue` passes it on
+            // to the parent's callback, the same as if there were no callback.
+            with_callbacks(ih, &K_ANY_CALLBACKS, |cbs| {
+                let key = Key::from_iup_code(c as u32);
+                let mut action = CallbackAction::Default;
+                for cb in cbs {
+                    match (&mut *cb.1.borrow_mut())(&key) {
+                        CallbackAction::Default => {},
+                        cb_action => action = cb_action,
+                    }
+                }
+                action.to_int()
+            })
+        }
+    }
 }
 
 
@@ -448,6 +610,13 @@ pub struct ButtonArgs {
     _dummy: (),
 }
 
+impl ButtonArgs {
+    pub fn new(button: MouseButton, pressed: bool, x: i32, y: i32, status: KeyboardMouseStatus)
+               -> ButtonArgs {
+        ButtonArgs { button: button, pressed: pressed, x: x, y: y, status: status, _dummy: () }
+    }
+}
+
 impl_callbacks! {
     trait ButtonCallback {
         "BUTTON_CB\0" => button_event {
@@ -527,6 +696,14 @@ impl CanvasActionArgs {
         }
     }
 
+    #[cfg(all(not(windows), not(feature = "cairo")))]
+    unsafe fn new(ih: *mut Ihandle, posx: c_float, posy: c_float) -> Self {
+        CanvasActionArgs {
+            pos: (posx, posy),
+            clip_rect: Self::get_clip_rect(ih),
+        }
+    }
+
     #[cfg(all(not(windows), feature = "cairo"))]
     unsafe fn new(ih: *mut Ihandle, posx: c_float, posy: c_float) -> Self {
         CanvasActionArgs {
@@ -557,6 +734,12 @@ pub struct MotionArgs {
     _dummy: (),
 }
 
+impl MotionArgs {
+    pub fn new(x: i32, y: i32, status: KeyboardMouseStatus) -> MotionArgs {
+        MotionArgs { x: x, y: y, status: status, _dummy: () }
+    }
+}
+
 #[derive(Clone)]
 pub struct WheelArgs {
     pub delta: f32,
@@ -660,3 +843,63 @@ impl_callbacks! {
         }
     }
 }
+
+#[derive(Clone)]
+pub struct DropFilesArgs {
+    pub path: PathBuf,
+    pub x: i32,
+    pub y: i32,
+    /// The number of files dropped at the same time that are still to come after this one. It is
+    /// 0 for the last file.
+    pub remaining: usize,
+    _dummy: (),
+}
+
+impl DropFilesArgs {
+    pub fn new(path: PathBuf, x: i32, y: i32, remaining: usize) -> DropFilesArgs {
+        DropFilesArgs { path: path, x: x, y: y, remaining: remaining, _dummy: () }
+    }
+}
+
+/// IUP only delivers file drops to a control with DROPFILESTARGET set, which it doesn't do on its
+/// own for a control that is already shown. Adding the first callback to a control sets it, so
+/// callbacks can be added before or after the control is shown.
+pub trait DropFilesCallback: Control {
+    fn drop_files_event<'a>(&'a self)
+                            -> Event<'a, FnMut(&DropFilesArgs) -> CallbackAction, DropFilesToken>
+    where &'a Self: CoerceUnsized<&'a Control> {
+        Event::new(self as &Control, &DROP_FILES_CALLBACKS)
+    }
+}
+
+callback_token!(DropFilesToken);
+thread_local!(
+    static DROP_FILES_CALLBACKS:
+        CallbackRegistry<FnMut(&DropFilesArgs) -> CallbackAction, DropFilesToken> =
+        CallbackRegistry::new("DROPFILES_CB\0", unsafe {
+            ::std::mem::transmute::<_, Icallback>(drop_files_cb as usize)
+        }).with_first_added(enable_drop_files)
+);
+
+fn enable_drop_files(ih: *mut Ihandle) {
+    ::attributes::set_str_attribute(ih, "DROPFILESTARGET\0", "YES\0");
+}
+
+unsafe extern fn drop_files_cb(ih: *mut Ihandle, filename: *const c_char, num: c_int, x: c_int,
+                               y: c_int) -> c_int {
+    // The callback is called once for each file dropped. Returning `Ignore` skips the rest of
+    // them.
+    with_callbacks(ih, &DROP_FILES_CALLBACKS, |cbs| {
+        // UTF8MODE_FILE is set, so file names are UTF-8.
+        let path = CStr::from_ptr(filename).to_string_lossy().into_owned();
+        let args = DropFilesArgs::new(PathBuf::from(path), x as i32, y as i32, num as usize);
+        let mut action = CallbackAction::Default;
+        for cb in cbs {
+            match (&mut *cb.1.borrow_mut())(&args) {
+                CallbackAction::Default => {},
+                cb_action => action = cb_action,
+            }
+        }
+        action.to_int()
+    })
+}
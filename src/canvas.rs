@@ -22,9 +22,13 @@ impl Canvas {
     pub fn draw_size(&self) -> (i32, i32) {
         get_int_int_attribute(self.handle(), "DRAWSIZE\0")
     }
+
+    pub fn try_draw_size(&self) -> Result<(i32, i32), Error> {
+        try_get_int_int_attribute(self.handle(), "DRAWSIZE\0")
+    }
 }
 
-impl_control_traits!(Canvas);
+impl_control_traits!(Canvas, "canvas");
 
 impl ActiveAttribute for Canvas {}
 impl CanFocusAttribute for Canvas {}
@@ -38,6 +42,10 @@ impl VisibleAttribute for Canvas {}
 
 impl MenuCommonCallbacks for Canvas {}
 impl GetKillFocusCallbacks for Canvas {}
+impl KAnyCallback for Canvas {}
+impl DropFilesCallback for Canvas {}
+impl DragSource for Canvas {}
+impl DropTarget for Canvas {}
 impl EnterLeaveWindowCallbacks for Canvas {}
 impl ButtonCallback for Canvas {}
 impl CanvasCallbacks for Canvas {}
@@ -0,0 +1,149 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::ffi::CStr;
+use std::slice;
+use libc::c_void;
+use super::control_prelude::*;
+use super::attributes::set_attribute_ptr;
+use super::Image;
+
+// This function is newer than iup-sys.
+extern {
+    fn IupGetNativeHandleImage(handle: *mut c_void) -> *mut Ihandle;
+}
+
+/// Access to the system clipboard.
+///
+/// Besides text and images, data can be copied in custom formats, which are identified by name.
+/// A custom format has to be registered with `add_format` before it is used. Other applications
+/// can read the data if they register a format with the same name.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use clear_coat::*;
+/// let clipboard = Clipboard::new();
+/// clipboard.set_text("Hello");
+/// assert_eq!(clipboard.text(), Some("Hello".to_owned()));
+///
+/// clipboard.add_format("application/x-my-shapes");
+/// clipboard.set_format_data("application/x-my-shapes", &[1, 2, 3]);
+/// ```
+#[derive(Clone)]
+pub struct Clipboard(HandleRc);
+
+impl Clipboard {
+    pub fn new() -> Self {
+        unsafe {
+            ::iup_open();
+            let ih = IupClipboard();
+            Clipboard(HandleRc::new(ih))
+        }
+    }
+
+    /// Returns the text on the clipboard, or `None` if there is no text.
+    pub fn text(&self) -> Option<String> {
+        let value = get_attribute_ptr(self.handle(), "TEXT\0");
+        if value.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(value).to_string_lossy().into_owned() })
+        }
+    }
+
+    /// Replaces the contents of the clipboard with text.
+    pub fn set_text(&self, text: &str) -> &Self {
+        set_str_attribute(self.handle(), "TEXT\0", text);
+        self
+    }
+
+    pub fn is_text_available(&self) -> bool {
+        self.is_available("TEXTAVAILABLE\0")
+    }
+
+    /// Returns the image on the clipboard, or `None` if there is no image.
+    pub fn image(&self) -> Option<Image> {
+        unsafe {
+            // This is how IUP's examples paste an image.
+            let native = get_attribute_ptr(self.handle(), "NATIVEIMAGE\0");
+            if native.is_null() {
+                return None;
+            }
+            let ih = IupGetNativeHandleImage(native as *mut c_void);
+            if ih.is_null() { None } else { Some(Image::from_handle_unchecked(ih)) }
+        }
+    }
+
+    /// Replaces the contents of the clipboard with an image.
+    pub fn set_image(&self, image: &Image) -> &Self {
+        unsafe {
+            // This gives the image a global name if it doesn't have one.
+            set_attribute_handle(self.handle(), "IMAGE\0", image.handle());
+        }
+        self
+    }
+
+    pub fn is_image_available(&self) -> bool {
+        self.is_available("IMAGEAVAILABLE\0")
+    }
+
+    /// Registers a custom format so that it can be used with the other methods. Registering a
+    /// format more than once has no effect.
+    pub fn add_format(&self, format: &str) -> &Self {
+        set_str_attribute(self.handle(), "ADDFORMAT\0", format);
+        self
+    }
+
+    /// Returns true if the clipboard has data in a format registered with `add_format`.
+    pub fn is_format_available(&self, format: &str) -> bool {
+        set_str_attribute(self.handle(), "FORMAT\0", format);
+        self.is_available("FORMATAVAILABLE\0")
+    }
+
+    /// Returns the data in a format registered with `add_format`, or `None` if the clipboard
+    /// doesn't have data in that format.
+    pub fn format_data(&self, format: &str) -> Option<Vec<u8>> {
+        let ih = self.handle();
+        set_str_attribute(ih, "FORMAT\0", format);
+        let data = get_attribute_ptr(ih, "FORMATDATA\0");
+        if data.is_null() {
+            return None;
+        }
+        // Reading FORMATDATA sets FORMATDATASIZE.
+        let size = get_parsed_attribute(ih, "FORMATDATASIZE\0", |s| s.parse::<usize>().ok())
+                   .unwrap_or(0);
+        Some(unsafe { slice::from_raw_parts(data as *const u8, size) }.to_owned())
+    }
+
+    /// Replaces the contents of the clipboard with data in a format registered with
+    /// `add_format`.
+    pub fn set_format_data(&self, format: &str, data: &[u8]) -> &Self {
+        let ih = self.handle();
+        set_str_attribute(ih, "FORMAT\0", format);
+        set_str_attribute(ih, "FORMATDATASIZE\0", &data.len().to_string());
+        // IUP copies the data to the clipboard immediately.
+        unsafe {
+            set_attribute_ptr(ih, "FORMATDATA\0", data.as_ptr() as *const c_char);
+        }
+        self
+    }
+
+    /// Removes everything from the clipboard.
+    pub fn clear(&self) -> &Self {
+        unsafe {
+            set_attribute_ptr(self.handle(), "TEXT\0", ptr::null());
+        }
+        self
+    }
+
+    fn is_available(&self, name: &str) -> bool {
+        unsafe { get_str_attribute_slice(self.handle(), name) == "YES" }
+    }
+}
+
+impl_control_traits!(Clipboard, "clipboard");
@@ -11,26 +11,18 @@ use super::attributes::*;
 pub trait Container : Control {
     /// Warning: Since children are stored as a linked list, appending a control is O(n) where
     /// n is the number of children.
-    fn append(&self, new_child: &Control) -> Result<(), ()> {
+    fn append(&self, new_child: &Control) -> Result<(), Error> {
         unsafe {
-            if IupAppend(self.handle(), new_child.handle()) == ptr::null_mut() {
-                Err(())
-            } else {
-                Ok(())
-            }
+            Error::check_handle("IupAppend", IupAppend(self.handle(), new_child.handle()))
         }
     }
 
     /// Warning: Since children are stored as a linked list, inserting a control is O(n) where
     /// n is the number of children before `ref_child`.
-    fn insert(&self, ref_child: Option<&Control>, new_child: &Control) -> Result<(), ()> {
+    fn insert(&self, ref_child: Option<&Control>, new_child: &Control) -> Result<(), Error> {
         unsafe {
             let ref_child = ref_child.map(|c| c.handle()).unwrap_or(ptr::null_mut());
-            if IupInsert(self.handle(), ref_child, new_child.handle()) == ptr::null_mut() {
-                Err(())
-            } else {
-                Ok(())
-            }
+            Error::check_handle("IupInsert", IupInsert(self.handle(), ref_child, new_child.handle()))
         }
     }
 
@@ -43,56 +35,68 @@ pub trait Container : Control {
             IupGetChildCount(self.handle()) as usize
         }
     }
-}
 
-pub trait NonDialogContainer : Container {
-    fn refresh_children(&self) {
+    /// Returns an iterator over the children of the specified control. Iterating over all of
+    /// them is O(n).
+    fn children(&self) -> Children {
         unsafe {
-            IupRefreshChildren(self.handle());
+            Children {
+                parent: ::AnyControl::from_handle(self.handle()),
+                prev: None,
+                done: false,
+            }
         }
     }
 }
 
-pub trait HVBox : Control {
-    fn expand_children(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "EXPANDCHILDREN\0") == "YES"
-        }
-    }
+/// An iterator over the children of a container. See `Container::children`.
+///
+/// If the last child returned is detached during iteration, the iteration ends early.
+pub struct Children {
+    parent: ::AnyControl,
+    prev: Option<::AnyControl>,
+    done: bool,
+}
 
-    fn set_expand_children(&self, expand: bool) -> &Self {
-        set_str_attribute(self.handle(), "EXPANDCHILDREN\0", if expand { "YES\0" } else { "NO\0" });
-        self
-    }
+impl Iterator for Children {
+    type Item = ::AnyControl;
 
-    fn gap(&self) -> u32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "GAP\0");
-            s.parse().expect("could not convert GAP to an integer")
+    fn next(&mut self) -> Option<::AnyControl> {
+        if self.done {
+            return None;
         }
+        // The previous child is kept alive so that getting its brother is safe even if it was
+        // detached.
+        let child = unsafe {
+            match self.prev {
+                Some(ref prev) => IupGetBrother(prev.handle()),
+                None => IupGetNextChild(self.parent.handle(), ptr::null_mut()),
+            }
+        };
+        if child.is_null() {
+            self.done = true;
+            self.prev = None;
+            return None;
+        }
+        let child = unsafe { ::AnyControl::from_handle(child) };
+        self.prev = Some(child.clone());
+        Some(child)
     }
+}
 
-    fn set_gap(&self, gap: u32) -> &Self {
-        set_str_attribute(self.handle(), "GAP\0", &format!("{}\0", gap));
-        self
-    }
-
-    fn ngap(&self) -> u32 {
+pub trait NonDialogContainer : Container {
+    fn refresh_children(&self) {
         unsafe {
-            let s = get_str_attribute_slice(self.handle(), "NGAP\0");
-            s.parse().expect("could not convert NGAP to an integer")
+            IupRefreshChildren(self.handle());
         }
     }
+}
 
-    fn set_ngap(&self, ngap: u32) -> &Self {
-        set_str_attribute(self.handle(), "NGAP\0", &format!("{}\0", ngap));
-        self
-    }
-
-    fn margin(&self) -> (u32, u32) {
-        let (x, y) = get_int_int_attribute(self.handle(), "MARGIN\0");
-        (x as u32, y as u32)
-    }
+pub trait HVBox : Control {
+    attr_methods!(expand_children, try_expand_children, set_expand_children: bool = attr::EXPANDCHILDREN);
+    attr_methods!(gap, try_gap, set_gap: u32 = attr::GAP);
+    attr_methods!(ngap, try_ngap, set_ngap: u32 = attr::NGAP);
+    attr_methods!(margin, try_margin: (u32, u32) = attr::MARGIN);
 
     fn set_margin(&self, width: u32, height: u32) -> &Self {
         let s = format!("{}x{}\0", width, height);
@@ -100,10 +104,7 @@ pub trait HVBox : Control {
         self
     }
 
-    fn nmargin(&self) -> (u32, u32) {
-        let (x, y) = get_int_int_attribute(self.handle(), "NMARGIN\0");
-        (x as u32, y as u32)
-    }
+    attr_methods!(nmargin, try_nmargin: (u32, u32) = attr::NMARGIN);
 
     fn set_nmargin(&self, width: u32, height: u32) -> &Self {
         let s = format!("{}x{}\0", width, height);
@@ -111,12 +112,7 @@ pub trait HVBox : Control {
         self
     }
 
-    fn normalize_size(&self) -> Orientations {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "NORMALIZESIZE\0");
-            Orientations::from_str(&s)
-        }
-    }
+    attr_methods!(normalize_size, try_normalize_size: Orientations = attr::NORMALIZESIZE);
 
     fn set_normalize_size(&self, orientations: Orientations) -> &Self {
         set_str_attribute(self.handle(), "NORMALIZESIZE\0", orientations.to_str());
@@ -153,7 +149,7 @@ impl Fill {
     }
 }
 
-impl_control_traits!(Fill);
+impl_control_traits!(Fill, "fill");
 
 // SizeAttribute instead of SingleSizeAttribute because it can be in a GridBox, where both the
 // width and height can be set.
@@ -196,7 +192,7 @@ impl Hbox {
     }
 }
 
-impl_control_traits!(Hbox);
+impl_control_traits!(Hbox, "hbox");
 
 impl Container for Hbox {}
 impl NonDialogContainer for Hbox {}
@@ -256,7 +252,7 @@ impl Vbox {
     }
 }
 
-impl_control_traits!(Vbox);
+impl_control_traits!(Vbox, "vbox");
 
 impl Container for Vbox {}
 impl NonDialogContainer for Vbox {}
@@ -323,10 +319,12 @@ impl GridBox {
     }
 
     pub fn alignment_lin(&self, line: u32) -> ::VAlignment {
-        unsafe {
-            let attr = format!("ALIGNMENTLIN{}\0", line);
-            ::VAlignment::from_str(get_str_attribute_slice(self.handle(), &attr).as_bytes())
-        }
+        self.try_alignment_lin(line).expect("unknown VAlignment")
+    }
+
+    pub fn try_alignment_lin(&self, line: u32) -> Result<::VAlignment, Error> {
+        let attr = format!("ALIGNMENTLIN{}\0", line);
+        get_parsed_attribute(self.handle(), &attr, |s| ::VAlignment::from_str(s.as_bytes()))
     }
 
     pub fn set_alignment_lin(&self, line: u32, alignment: ::VAlignment) -> &Self {
@@ -334,23 +332,15 @@ impl GridBox {
         self
     }
 
-    pub fn alignment_lin_all(&self) -> ::VAlignment {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "ALIGNMENTLIN\0");
-            ::VAlignment::from_str(s.as_bytes())
-        }
-    }
+    attr_methods!(pub alignment_lin_all, try_alignment_lin_all, set_alignment_lin_all: ::VAlignment = attr::ALIGNMENTLIN);
 
-    pub fn set_alignment_lin_all(&self, alignment: ::VAlignment) -> &Self {
-        set_str_attribute(self.handle(), "ALIGNMENTLIN\0", alignment.to_str());
-        self
+    pub fn alignment_col(&self, column: u32) -> ::HAlignment {
+        self.try_alignment_col(column).expect("unknown HAlignment")
     }
 
-    pub fn alignment_col(&self, column: u32) -> ::HAlignment {
-        unsafe {
-            let attr = format!("ALIGNMENTCOL{}\0", column);
-            ::HAlignment::from_str(get_str_attribute_slice(self.handle(), &attr).as_bytes())
-        }
+    pub fn try_alignment_col(&self, column: u32) -> Result<::HAlignment, Error> {
+        let attr = format!("ALIGNMENTCOL{}\0", column);
+        get_parsed_attribute(self.handle(), &attr, |s| ::HAlignment::from_str(s.as_bytes()))
     }
 
     pub fn set_alignment_col(&self, column: u32, alignment: ::HAlignment) -> &Self {
@@ -358,27 +348,20 @@ impl GridBox {
         self
     }
 
-    pub fn alignment_col_all(&self) -> ::HAlignment {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "ALIGNMENTCOL\0");
-            ::HAlignment::from_str(s.as_bytes())
-        }
-    }
+    attr_methods!(pub alignment_col_all, try_alignment_col_all, set_alignment_col_all: ::HAlignment = attr::ALIGNMENTCOL);
 
-    pub fn set_alignment_col_all(&self, alignment: ::HAlignment) -> &Self {
-        set_str_attribute(self.handle(), "ALIGNMENTCOL\0", alignment.to_str());
-        self
+    pub fn num_div(&self) -> NumDiv {
+        self.try_num_div().expect("could not convert NUMDIV to an integer")
     }
 
-    pub fn num_div(&self) -> NumDiv {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "NUMDIV\0");
+    pub fn try_num_div(&self) -> Result<NumDiv, Error> {
+        get_parsed_attribute(self.handle(), "NUMDIV\0", |s| {
             if s.as_bytes() == b"-1" {
-                NumDiv::Auto
+                Some(NumDiv::Auto)
             } else {
-                NumDiv::Fixed(s.parse().expect("could not convert NUMDIV to an integer"))
+                s.parse().ok().map(NumDiv::Fixed)
             }
-        }
+        })
     }
 
     pub fn set_num_div(&self, num: NumDiv) -> &Self {
@@ -389,19 +372,8 @@ impl GridBox {
         self
     }
 
-    pub fn num_lin(&self) -> u32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "NUMLIN\0");
-            s.parse().expect("could not convert NUMLIN to an integer")
-        }
-    }
-
-    pub fn num_col(&self) -> u32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "NUMCOL\0");
-            s.parse().expect("could not convert NUMLIN to an integer")
-        }
-    }
+    attr_methods!(pub num_lin, try_num_lin: u32 = attr::NUMLIN);
+    attr_methods!(pub num_col, try_num_col: u32 = attr::NUMCOL);
 
     pub fn fit_col_to_children(&self, column: u32) -> &Self {
         set_str_attribute(self.handle(), "FITTOCHILDREN\0", &format!("C{}\0", column));
@@ -423,32 +395,11 @@ impl GridBox {
         self
     }
 
-    pub fn size_col(&self) -> u32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "SIZECOL\0");
-            s.parse().expect("could not convert SIZECOL to an integer")
-        }
-    }
-
-    pub fn set_size_col(&self, column: u32) -> &Self {
-        set_str_attribute(self.handle(), "SIZECOL\0", &format!("{}\0", column));
-        self
-    }
-
-    pub fn size_lin(&self) -> u32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "SIZELIN\0");
-            s.parse().expect("could not convert SIZELIN to an integer")
-        }
-    }
-
-    pub fn set_size_lin(&self, line: u32) -> &Self {
-        set_str_attribute(self.handle(), "SIZELIN\0", &format!("{}\0", line));
-        self
-    }
+    attr_methods!(pub size_col, try_size_col, set_size_col: u32 = attr::SIZECOL);
+    attr_methods!(pub size_lin, try_size_lin, set_size_lin: u32 = attr::SIZELIN);
 }
 
-impl_control_traits!(GridBox);
+impl_control_traits!(GridBox, "gridbox");
 
 impl Container for GridBox {}
 impl NonDialogContainer for GridBox {}
@@ -15,14 +15,18 @@ pub use iup_sys::*;
 pub use smallvec::SmallVec;
 pub use super::{
     Control,
+    ControlType,
     UnwrapHandle,
 };
+pub use super::attr::{self, Attr, AttrValue};
 pub use super::attributes::{
     str_to_c_vec,
     get_attribute_ptr,
     get_str_attribute,
     get_str_attribute_slice,
     get_int_int_attribute,
+    get_parsed_attribute,
+    try_get_int_int_attribute,
     set_str_attribute,
     set_attribute_handle,
     reset_attribute,
@@ -31,8 +35,11 @@ pub use super::attributes::{
     CanvasAttributes,
     CursorAttribute,
     ExpandAttribute,
+    ImageAttribute,
+    InactiveImageAttribute,
     MinMaxSizeAttribute,
     OrientationAttribute,
+    PressedImageAttribute,
     ScrollbarAttribute,
     TipAttribute,
     TitleAttribute,
@@ -48,10 +55,14 @@ pub use super::callbacks::{
     EnterLeaveWindowCallbacks,
     GetKillFocusCallbacks,
     ButtonCallback,
+    KAnyCallback,
+    DropFilesCallback,
     ValueChangedCallback,
     CanvasCallbacks,
     ResizeCallback,
     Event,
     Token,
 };
+pub use super::drag_drop::{DragSource, DropTarget};
+pub use super::error::Error;
 pub use super::handle_rc::HandleRc;
@@ -7,14 +7,25 @@
 
 use super::control_prelude::*;
 use std::ffi::CStr;
+use std::future::Future;
 use std::mem;
+use std::pin::Pin;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::task::{Context, Poll};
 use super::{
     Button,
+    Image,
+    Item,
+    Key,
     ScreenPosition,
     Menu,
     Popup,
 };
+use super::image::{get_image_attribute, set_image_attribute};
+use super::shortcuts;
 use super::containers::Container;
+use super::executor::Signal;
 use super::extra_refs::{
     ExtraRefKey,
     add_extra_ref,
@@ -25,6 +36,7 @@ use super::extra_refs::{
 pub struct Dialog(HandleRc);
 
 const EXTRA_REF_MENU: ExtraRefKey = ExtraRefKey(0);
+const EXTRA_REF_ICON: ExtraRefKey = ExtraRefKey(5);
 
 impl Dialog {
     pub fn new() -> Dialog {
@@ -53,13 +65,26 @@ impl Dialog {
         Dialog(HandleRc::new(handle))
     }
 
-    pub fn show_xy(&self, x: ScreenPosition, y: ScreenPosition) -> Result<(), ()> {
+    /// Finds the control in this dialog whose NAME is `name`. Returns an error if there isn't one
+    /// or if it isn't a `T`.
+    ///
+    /// ```no_run
+    /// # use clear_coat::*;
+    /// let email = Text::new();
+    /// email.set_name("email");
+    /// let dialog = Dialog::with_child(&email);
+    /// let found = dialog.find::<Text>("email").unwrap();
+    /// ```
+    pub fn find<T: ControlType>(&self, name: &str) -> Result<T, Error> {
+        let mut name_buf = SmallVec::<[u8; 64]>::new();
+        let c_name = str_to_c_vec(name, &mut name_buf);
+        let ih = unsafe { IupGetDialogChild(self.handle(), c_name) };
+        ::any_control::wrap_named(ih, name)
+    }
+
+    pub fn show_xy(&self, x: ScreenPosition, y: ScreenPosition) -> Result<(), Error> {
         unsafe {
-            if IupShowXY(self.handle(), x.to_int(), y.to_int()) == IUP_NOERROR {
-                Ok(())
-            } else {
-                Err(())
-            }
+            Error::check("IupShowXY", IupShowXY(self.handle(), x.to_int(), y.to_int()))
         }
     }
 
@@ -109,10 +134,121 @@ impl Dialog {
         }
     }
 
+    pub fn icon(&self) -> Option<Image> {
+        get_image_attribute(self.handle(), "ICON\0")
+    }
+
+    /// Sets the icon shown in the dialog's title bar and the taskbar, or uses the default icon if
+    /// `icon` is `None`. The dialog keeps a reference to the image.
+    pub fn set_icon(&self, icon: Option<&Image>) -> &Self {
+        set_image_attribute(self.handle(), "ICON\0", EXTRA_REF_ICON, icon);
+        self
+    }
+
+    /// Calls `f` whenever the key `shortcut`, such as "Ctrl+S" or "F5", is pressed in this dialog.
+    /// See `Key` for how keys are written.
+    ///
+    /// The shortcut works whichever child has the focus, unless the child handles the key itself,
+    /// including in its own `k_any_event` callback (returning `CallbackAction::Continue` from it
+    /// passes the key on). Returns an error if `shortcut` isn't a valid key or if the dialog
+    /// already has a shortcut for it. The shortcut is kept until it is removed or the dialog is
+    /// destroyed.
+    pub fn add_shortcut<F: FnMut() + 'static>(&self, shortcut: &str, f: F) -> Result<(), Error> {
+        let key = shortcut.parse::<Key>()?;
+        shortcuts::add_shortcut(self, key, Rc::new(RefCell::new(f)))
+    }
+
+    /// Adds a shortcut that activates a menu item, the same as clicking it, and shows the
+    /// shortcut's text in the item. The item isn't activated if it is inactive.
+    ///
+    /// ```no_run
+    /// # use clear_coat::*;
+    /// let save = Item::with_title("Save");
+    /// save.action_event().add(|| println!("saved"));
+    /// let dialog = Dialog::new();
+    /// dialog.set_menu(Some(&Menu::with_children(&[&save])));
+    /// dialog.add_item_shortcut("Ctrl+S", &save).unwrap();
+    /// ```
+    pub fn add_item_shortcut(&self, shortcut: &str, item: &Item) -> Result<(), Error> {
+        let key = shortcut.parse::<Key>()?;
+        let weak_item = item.downgrade();
+        shortcuts::add_shortcut(self, key, Rc::new(RefCell::new(move || {
+            if let Some(item) = weak_item.upgrade() {
+                item.activate();
+            }
+        })))?;
+        item.set_shortcut_text(Some(&key));
+        Ok(())
+    }
+
+    /// Removes a shortcut added with `add_shortcut` or `add_item_shortcut`. Returns false if the
+    /// dialog has no shortcut for the key, or an error if `shortcut` isn't a valid key.
+    ///
+    /// The text shown in a menu item is not removed.
+    pub fn remove_shortcut(&self, shortcut: &str) -> Result<bool, Error> {
+        let key = shortcut.parse::<Key>()?;
+        Ok(shortcuts::remove_shortcut(self, &key))
+    }
+
+    /// Returns the keys of all the dialog's shortcuts, in no particular order.
+    pub fn shortcuts(&self) -> Vec<Key> {
+        shortcuts::shortcut_keys(self)
+    }
+
     pub fn show_event<'a>(&'a self) -> Event<'a, FnMut(ShowState) -> CallbackAction, ShowCallbackToken>
     where &'a Self: CoerceUnsized<&'a Control> {
         Event::new(self as &Control, &SHOW_CALLBACKS)
     }
+
+    /// Returns a future that completes the next time this dialog is hidden, such as by the user
+    /// closing it. See the `executor` module.
+    pub fn closed(&self) -> DialogClosed {
+        let signal = Signal::new();
+        let signal2 = signal.clone();
+        let token = self.show_event().add(move |state| {
+            if state == ShowState::Hide {
+                signal2.fire();
+            }
+            CallbackAction::Default
+        });
+        DialogClosed { dialog: self.clone(), token: Some(token), signal: signal }
+    }
+}
+
+/// A future that completes when a dialog is hidden. See `Dialog::closed`.
+pub struct DialogClosed {
+    dialog: Dialog,
+    token: Option<ShowCallbackToken>,
+    signal: Rc<Signal>,
+}
+
+impl DialogClosed {
+    fn remove_handler(&mut self) {
+        // The handler was already removed if the dialog was destroyed.
+        if let Some(token) = self.token.take() {
+            if !self.dialog.0.get().is_null() {
+                self.dialog.show_event().remove(token);
+            }
+        }
+    }
+}
+
+impl Future for DialogClosed {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let poll = self.signal.poll(cx);
+        if poll.is_ready() {
+            self.get_mut().remove_handler();
+        }
+        poll
+    }
+}
+
+impl Drop for DialogClosed {
+    fn drop(&mut self) {
+        self.remove_handler();
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -168,7 +304,7 @@ extern fn show_cb(ih: *mut Ihandle, state: c_int) -> c_int {
     })
 }
 
-impl_control_traits!(Dialog);
+impl_control_traits!(Dialog, "dialog");
 
 impl Container for Dialog {}
 impl Popup for Dialog {}
@@ -183,5 +319,8 @@ impl VisibleAttribute for Dialog {}
 
 impl MenuCommonCallbacks for Dialog {}
 impl GetKillFocusCallbacks for Dialog {}
+impl KAnyCallback for Dialog {}
+impl DropFilesCallback for Dialog {}
+impl DropTarget for Dialog {}
 impl EnterLeaveWindowCallbacks for Dialog {}
 impl ResizeCallback for Dialog {}
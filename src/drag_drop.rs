@@ -0,0 +1,300 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::slice;
+use libc::c_void;
+use super::control_prelude::*;
+use super::callbacks::handle_callback_panic;
+use super::handle_rc::add_ldestroy_callback;
+
+/// A type of data that can be dragged between controls.
+///
+/// `TYPE` names the data's format. A drop target only accepts data with one of the names it has
+/// added, and dragging between applications only works if both use the same name for a format.
+/// "TEXT" is the common name for plain text, which `String` uses.
+///
+/// # Examples
+///
+/// ```
+/// # use clear_coat::*;
+/// struct Color(u8, u8, u8);
+///
+/// impl DragData for Color {
+///     const TYPE: &'static str = "application/x-my-color";
+///
+///     fn to_bytes(&self) -> Vec<u8> {
+///         vec![self.0, self.1, self.2]
+///     }
+///
+///     fn from_bytes(bytes: &[u8]) -> Option<Color> {
+///         match bytes {
+///             &[r, g, b] => Some(Color(r, g, b)),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait DragData : Sized + 'static {
+    const TYPE: &'static str;
+
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Returns `None` if the data is not valid, in which case the drop is ignored.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+impl DragData for String {
+    const TYPE: &'static str = "TEXT";
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_owned()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<String> {
+        // Some systems include the null terminator in the data.
+        let bytes = if bytes.last() == Some(&0) { &bytes[..bytes.len() - 1] } else { bytes };
+        Some(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+#[derive(Clone)]
+pub struct DragBeginArgs {
+    /// The position where the drag started, relative to the control.
+    pub x: i32,
+    pub y: i32,
+    _dummy: (),
+}
+
+#[derive(Clone)]
+pub struct DropArgs {
+    /// The position of the drop, relative to the control.
+    pub x: i32,
+    pub y: i32,
+    _dummy: (),
+}
+
+struct DragSourceState {
+    type_name: &'static str,
+    // `None` after `remove_drag_source`.
+    begin: Option<Rc<RefCell<FnMut(&DragBeginArgs) -> Option<Vec<u8>>>>>,
+    // The data from `begin`, kept until the drag ends.
+    data: Option<Vec<u8>>,
+}
+
+type DropHandler = Rc<RefCell<FnMut(&[u8], &DropArgs) -> bool>>;
+
+// Like extra refs, these are removed when the control is destroyed.
+thread_local!(
+    static DRAG_SOURCES: RefCell<HashMap<*mut Ihandle, DragSourceState>> =
+        RefCell::new(HashMap::new())
+);
+thread_local!(
+    static DROP_TARGETS: RefCell<HashMap<*mut Ihandle, Vec<(&'static str, DropHandler)>>> =
+        RefCell::new(HashMap::new())
+);
+
+// Runs the part of a drag and drop callback that calls user code, the same way `with_callbacks`
+// does.
+fn catch_panic<F: FnOnce() -> c_int>(f: F) -> c_int {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            handle_callback_panic(payload);
+            IUP_DEFAULT
+        },
+    }
+}
+
+fn set_callback(ih: *mut Ihandle, name: &str, cb: usize) {
+    let mut buf = SmallVec::<[u8; 32]>::new();
+    unsafe {
+        IupSetCallback(ih, str_to_c_vec(name, &mut buf), mem::transmute::<_, Icallback>(cb));
+    }
+}
+
+fn is_type(type_name: *const c_char, expected: &str) -> bool {
+    !type_name.is_null() && unsafe { CStr::from_ptr(type_name).to_bytes() } == expected.as_bytes()
+}
+
+extern fn drag_begin_cb(ih: *mut Ihandle, x: c_int, y: c_int) -> c_int {
+    let begin = DRAG_SOURCES.with(|map| {
+        map.borrow().get(&ih).and_then(|state| state.begin.clone())
+    });
+    let begin = match begin {
+        Some(begin) => begin,
+        None => return IUP_IGNORE,
+    };
+    catch_panic(|| {
+        let args = DragBeginArgs { x: x as i32, y: y as i32, _dummy: () };
+        let data = (&mut *begin.borrow_mut())(&args);
+        let started = data.is_some();
+        DRAG_SOURCES.with(|map| {
+            if let Some(state) = map.borrow_mut().get_mut(&ih) {
+                state.data = data;
+            }
+        });
+        if started { IUP_DEFAULT } else { IUP_IGNORE }
+    })
+}
+
+fn with_drag_data<F: FnOnce(&[u8]) -> c_int>(ih: *mut Ihandle, type_name: *const c_char, f: F)
+                                             -> c_int {
+    DRAG_SOURCES.with(|map| {
+        match map.borrow().get(&ih) {
+            Some(state) if is_type(type_name, state.type_name) =>
+                state.data.as_ref().map_or(0, |data| f(data)),
+            _ => 0,
+        }
+    })
+}
+
+extern fn drag_data_size_cb(ih: *mut Ihandle, type_name: *mut c_char) -> c_int {
+    with_drag_data(ih, type_name, |data| data.len() as c_int)
+}
+
+extern fn drag_data_cb(ih: *mut Ihandle, type_name: *mut c_char, data: *mut c_void, size: c_int)
+                       -> c_int {
+    with_drag_data(ih, type_name, |bytes| {
+        let len = bytes.len().min(size.max(0) as usize);
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), data as *mut u8, len); }
+        IUP_DEFAULT
+    })
+}
+
+extern fn drag_end_cb(ih: *mut Ihandle, _action: c_int) -> c_int {
+    DRAG_SOURCES.with(|map| {
+        if let Some(state) = map.borrow_mut().get_mut(&ih) {
+            state.data = None;
+        }
+    });
+    IUP_DEFAULT
+}
+
+extern fn drop_data_cb(ih: *mut Ihandle, type_name: *mut c_char, data: *mut c_void, size: c_int,
+                       x: c_int, y: c_int) -> c_int {
+    let handlers = DROP_TARGETS.with(|map| {
+        map.borrow().get(&ih).map_or(vec![], |handlers| {
+            handlers.iter().filter(|&&(t, _)| is_type(type_name, t)).map(|&(_, ref h)| h.clone())
+                    .collect::<Vec<_>>()
+        })
+    });
+    let bytes = if data.is_null() || size <= 0 {
+        &[][..]
+    } else {
+        unsafe { slice::from_raw_parts(data as *const u8, size as usize) }
+    };
+    catch_panic(|| {
+        let args = DropArgs { x: x as i32, y: y as i32, _dummy: () };
+        for handler in handlers {
+            if (&mut *handler.borrow_mut())(bytes, &args) {
+                break;
+            }
+        }
+        IUP_DEFAULT
+    })
+}
+
+/// Implemented by controls that data can be dragged from.
+pub trait DragSource : Control {
+    /// Makes the control a drag source for data of type `T`. When the user starts dragging from
+    /// the control, `begin` is called to get the data, or `None` to not start a drag. It replaces
+    /// any function set before.
+    fn set_drag_source<T, F>(&self, mut begin: F) -> &Self
+    where Self: Sized, T: DragData, F: FnMut(&DragBeginArgs) -> Option<T> + 'static {
+        let ih = self.handle();
+        let begin = Rc::new(RefCell::new(move |args: &DragBeginArgs| {
+            begin(args).map(|data| data.to_bytes())
+        }));
+        let state = DragSourceState { type_name: T::TYPE, begin: Some(begin), data: None };
+        let old = DRAG_SOURCES.with(|map| {
+            let mut map = map.borrow_mut();
+            if !map.contains_key(&ih) {
+                add_ldestroy_callback(ih, |ih| {
+                    let removed = DRAG_SOURCES.with(|map| map.borrow_mut().remove(&ih));
+                    drop(removed);
+                });
+            }
+            map.insert(ih, state)
+        });
+        drop(old);
+        set_str_attribute(ih, "DRAGTYPES\0", T::TYPE);
+        set_str_attribute(ih, "DRAGSOURCE\0", "YES\0");
+        set_callback(ih, "DRAGBEGIN_CB\0", drag_begin_cb as usize);
+        set_callback(ih, "DRAGDATASIZE_CB\0", drag_data_size_cb as usize);
+        set_callback(ih, "DRAGDATA_CB\0", drag_data_cb as usize);
+        set_callback(ih, "DRAGEND_CB\0", drag_end_cb as usize);
+        self
+    }
+
+    /// Stops the control from being a drag source.
+    fn remove_drag_source(&self) {
+        set_str_attribute(self.handle(), "DRAGSOURCE\0", "NO\0");
+        // The state is kept so that the LDESTROY callback isn't added again if the control is
+        // made a drag source again.
+        let removed = DRAG_SOURCES.with(|map| {
+            map.borrow_mut().get_mut(&self.handle()).and_then(|state| state.begin.take())
+        });
+        drop(removed);
+    }
+}
+
+/// Implemented by controls that data can be dropped on.
+pub trait DropTarget : Control {
+    /// Accepts data of type `T` being dropped on the control, calling `dropped` with it. A control
+    /// can accept several types, and if data is offered in more than one of them, the first one
+    /// added is used.
+    fn add_drop_target<T, F>(&self, mut dropped: F) -> &Self
+    where Self: Sized, T: DragData, F: FnMut(T, &DropArgs) + 'static {
+        let ih = self.handle();
+        let handler: DropHandler = Rc::new(RefCell::new(move |bytes: &[u8], args: &DropArgs| {
+            match T::from_bytes(bytes) {
+                Some(data) => {
+                    dropped(data, args);
+                    true
+                },
+                None => false,
+            }
+        }));
+        let types = DROP_TARGETS.with(|map| {
+            let mut map = map.borrow_mut();
+            let handlers = map.entry(ih).or_insert_with(|| {
+                add_ldestroy_callback(ih, |ih| {
+                    let removed = DROP_TARGETS.with(|map| map.borrow_mut().remove(&ih));
+                    drop(removed);
+                });
+                vec![]
+            });
+            handlers.push((T::TYPE, handler));
+            let mut types: Vec<&str> = vec![];
+            for &(t, _) in handlers.iter() {
+                if !types.contains(&t) {
+                    types.push(t);
+                }
+            }
+            types.join(",")
+        });
+        set_str_attribute(ih, "DROPTYPES\0", &types);
+        set_str_attribute(ih, "DROPTARGET\0", "YES\0");
+        set_callback(ih, "DROPDATA_CB\0", drop_data_cb as usize);
+        self
+    }
+
+    /// Stops accepting any drops added with `add_drop_target`.
+    fn remove_drop_targets(&self) {
+        set_str_attribute(self.handle(), "DROPTARGET\0", "NO\0");
+        let removed = DROP_TARGETS.with(|map| {
+            map.borrow_mut().get_mut(&self.handle()).map(|handlers| mem::replace(handlers, vec![]))
+        });
+        drop(removed);
+    }
+}
@@ -0,0 +1,121 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::error;
+use std::fmt;
+use libc::c_int;
+use iup_sys::*;
+
+/// An error returned by an IUP function or found while reading an attribute.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// An IUP function failed. `code` is the value it returned, such as `IUP_ERROR` or
+    /// `IUP_INVALID`, or `None` if it returned a null handle.
+    Iup {
+        function: &'static str,
+        code: Option<c_int>,
+    },
+    /// An attribute had a value that couldn't be converted to the type it is supposed to be.
+    /// This can happen if a newer version of IUP adds a value or if a value was set from a LED
+    /// file or by name.
+    InvalidAttribute {
+        name: String,
+        value: String,
+    },
+    /// LED could not be loaded. `message` is the error IUP reported, which includes the line
+    /// number of a syntax error.
+    Led {
+        message: String,
+    },
+    /// No control has the name that was looked up.
+    NameNotFound {
+        name: String,
+    },
+    /// The control with the name that was looked up is not of the type it was requested as.
+    /// `expected` and `actual` are class names, such as "text" or "button".
+    WrongClass {
+        name: String,
+        expected: &'static str,
+        actual: String,
+    },
+    /// Text could not be parsed as a `Key`.
+    InvalidKey {
+        text: String,
+    },
+    /// A shortcut was added to a dialog that already has one for the same key. `key` is the key
+    /// written the way `Key` displays it.
+    DuplicateShortcut {
+        key: String,
+    },
+    /// An image file could not be read or decoded. `message` describes the problem.
+    ImageFile {
+        message: String,
+    },
+}
+
+impl Error {
+    // Converts the return value of functions like `IupShow` into a `Result`.
+    pub(crate) fn check(function: &'static str, code: c_int) -> Result<(), Error> {
+        if code == IUP_NOERROR {
+            Ok(())
+        } else {
+            Err(Error::Iup { function: function, code: Some(code) })
+        }
+    }
+
+    // Converts the return value of functions like `IupAppend` into a `Result`.
+    pub(crate) fn check_handle(function: &'static str, ih: *mut Ihandle) -> Result<(), Error> {
+        if ih.is_null() {
+            Err(Error::Iup { function: function, code: None })
+        } else {
+            Ok(())
+        }
+    }
+
+    // `name` can have a null terminator, so that the same string passed to
+    // `get_str_attribute_slice` can be passed here.
+    pub(crate) fn invalid_attribute(name: &str, value: &str) -> Error {
+        Error::InvalidAttribute {
+            name: name.trim_end_matches('\0').to_owned(),
+            value: value.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Iup { function, code: Some(code) } => write!(f, "{} returned {}", function, code),
+            Error::Iup { function, code: None } => write!(f, "{} returned a null handle", function),
+            Error::InvalidAttribute { ref name, ref value } =>
+                write!(f, "invalid value {:?} for attribute {}", value, name),
+            Error::Led { ref message } => write!(f, "failed to load LED: {}", message),
+            Error::NameNotFound { ref name } => write!(f, "no control is named {:?}", name),
+            Error::WrongClass { ref name, expected, ref actual } =>
+                write!(f, "the control named {:?} is a {}, not a {}", name, actual, expected),
+            Error::InvalidKey { ref text } => write!(f, "{:?} is not a valid key", text),
+            Error::DuplicateShortcut { ref key } =>
+                write!(f, "the dialog already has a shortcut for {}", key),
+            Error::ImageFile { ref message } => write!(f, "failed to load image: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Iup { .. } => "IUP function failed",
+            Error::InvalidAttribute { .. } => "invalid attribute value",
+            Error::Led { .. } => "failed to load LED",
+            Error::NameNotFound { .. } => "no control has the name",
+            Error::WrongClass { .. } => "named control has the wrong class",
+            Error::InvalidKey { .. } => "invalid key",
+            Error::DuplicateShortcut { .. } => "dialog already has a shortcut for the key",
+            Error::ImageFile { .. } => "failed to load image",
+        }
+    }
+}
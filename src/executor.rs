@@ -0,0 +1,204 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+//! Running futures on the UI thread.
+//!
+//! `spawn_local` runs a future on the main loop. Its waker can be called from any thread, and
+//! the task is polled again on the UI thread the next time the loop processes events. Along with
+//! `sleep`, `Dialog::closed`, and `Button::clicked`, this allows writing a sequence of steps that
+//! wait on the UI without nesting callbacks:
+//!
+//! ```no_run,edition2018
+//! # use clear_coat::*;
+//! # use clear_coat::executor::{spawn_local, sleep};
+//! # use clear_coat::common_attrs_cbs::*;
+//! # use std::time::Duration;
+//! # use std::future::Future;
+//! # fn run(dialog: Dialog, button: Button) -> impl Future<Output = ()> {
+//! async move {
+//!     button.clicked().await;
+//!     button.set_title("Wait...");
+//!     sleep(Duration::from_secs(1)).await;
+//!     dialog.hide();
+//! }
+//! # }
+//! # let dialog = Dialog::new();
+//! # let button = Button::new();
+//! spawn_local(run(dialog, button));
+//! main_loop();
+//! ```
+//!
+//! A task that panics ends the main loop, and the panic is propagated out of it. Tasks only make
+//! progress while a main loop (or `loop_step`) is running.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+use super::{Timer, UiSender};
+
+struct Task {
+    future: Pin<Box<Future<Output = ()>>>,
+    waker: Arc<TaskWaker>,
+}
+
+thread_local!(static TASKS: RefCell<HashMap<usize, Task>> = RefCell::new(HashMap::new()));
+thread_local!(static NEXT_TASK_ID: Cell<usize> = Cell::new(0));
+
+struct TaskWaker {
+    id: usize,
+    sender: UiSender,
+    // Set while a poll is posted and hasn't run yet, so that waking many times only polls once.
+    scheduled: AtomicBool,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.scheduled.swap(true, Ordering::SeqCst) {
+            let scheduled = ScheduledPoll(self.clone());
+            // If the main loop has exited, there is nothing to poll the task, so it is left
+            // pending.
+            let _ = self.sender.post(move || {
+                let id = scheduled.0.id;
+                // The task can be woken again while it is polled.
+                drop(scheduled);
+                poll_task(id);
+            });
+        }
+    }
+}
+
+// Clears `scheduled` when dropped, so that if a posted poll is dropped without running, such as
+// when the main loop exits, waking the task still works afterward.
+struct ScheduledPoll(Arc<TaskWaker>);
+
+impl Drop for ScheduledPoll {
+    fn drop(&mut self) {
+        self.0.scheduled.store(false, Ordering::SeqCst);
+    }
+}
+
+fn poll_task(id: usize) {
+    // The task is removed while it is polled so that it can spawn other tasks.
+    let task = TASKS.with(|tasks| tasks.borrow_mut().remove(&id));
+    let mut task = match task {
+        Some(task) => task,
+        None => return,
+    };
+    let waker = Waker::from(task.waker.clone());
+    let mut cx = Context::from_waker(&waker);
+    if task.future.as_mut().poll(&mut cx).is_pending() {
+        TASKS.with(|tasks| tasks.borrow_mut().insert(id, task));
+    }
+}
+
+/// Runs a future on the UI thread's main loop. It is first polled the next time the loop
+/// processes events.
+///
+/// This must be called on the UI thread.
+pub fn spawn_local<F: Future<Output = ()> + 'static>(future: F) {
+    let sender = UiSender::new();
+    let id = NEXT_TASK_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    });
+    let waker = Arc::new(TaskWaker { id: id, sender: sender, scheduled: AtomicBool::new(false) });
+    TASKS.with(|tasks| {
+        tasks.borrow_mut().insert(id, Task { future: Box::pin(future), waker: waker.clone() })
+    });
+    waker.wake();
+}
+
+// The state shared between a future and the callback that completes it.
+pub(crate) struct Signal {
+    fired: Cell<bool>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl Signal {
+    pub fn new() -> Rc<Signal> {
+        Rc::new(Signal { fired: Cell::new(false), waker: RefCell::new(None) })
+    }
+
+    pub fn fire(&self) {
+        self.fired.set(true);
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+
+    pub fn poll(&self, cx: &mut Context) -> Poll<()> {
+        if self.fired.get() {
+            Poll::Ready(())
+        } else {
+            *self.waker.borrow_mut() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// A future that completes after a duration. See `sleep`.
+pub struct Sleep {
+    timer: Option<Timer>,
+    signal: Rc<Signal>,
+}
+
+/// Returns a future that completes once `duration` has passed, using a `Timer`. The timer is
+/// stopped if the future is dropped first.
+pub fn sleep(duration: Duration) -> Sleep {
+    let signal = Signal::new();
+    // TIME is in whole milliseconds, so round up so as not to complete early.
+    let ms = duration.as_secs() * 1000 + (duration.subsec_nanos() as u64 + 999_999) / 1_000_000;
+    if ms == 0 {
+        signal.fire();
+        return Sleep { timer: None, signal: signal };
+    }
+    let timer = Timer::new();
+    timer.set_time(ms.min(u32::max_value() as u64) as u32);
+    // The callback can't stop the timer because capturing it would keep it alive forever. It is
+    // stopped once the future sees that it has fired.
+    let signal2 = signal.clone();
+    timer.action_event().add(move || signal2.fire());
+    timer.set_running(true);
+    Sleep { timer: Some(timer), signal: signal }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let poll = self.signal.poll(cx);
+        if poll.is_ready() {
+            self.get_mut().stop();
+        }
+        poll
+    }
+}
+
+impl Sleep {
+    fn stop(&mut self) {
+        if let Some(timer) = self.timer.take() {
+            timer.set_running(false);
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
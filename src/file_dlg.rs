@@ -19,12 +19,12 @@ pub enum FileDialogType {
 }
 
 impl FileDialogType {
-    fn from_str(s: &str) -> FileDialogType {
+    fn from_str(s: &str) -> Option<FileDialogType> {
         match s {
-            "OPEN" => FileDialogType::Open,
-            "SAVE" => FileDialogType::Save,
-            "DIR" => FileDialogType::Dir,
-            _ => panic!("unknown FileDialogType string"),
+            "OPEN" => Some(FileDialogType::Open),
+            "SAVE" => Some(FileDialogType::Save),
+            "DIR" => Some(FileDialogType::Dir),
+            _ => None,
         }
     }
 
@@ -37,6 +37,13 @@ impl FileDialogType {
     }
 }
 
+impl AttrValue for FileDialogType {
+    fn parse_attr(s: &str) -> Option<Self> { FileDialogType::from_str(s) }
+    fn format_attr(&self) -> Cow<'static, str> { self.to_str().into() }
+}
+
+const DIALOGTYPE: Attr<FileDialogType> = Attr::new("DIALOGTYPE\0");
+
 pub struct FileExtFilter<'a, 'b, 'c: 'b> {
     pub description: Cow<'a, str>,
     pub filter: Cow<'b, [Cow<'c, str>]>,
@@ -70,15 +77,10 @@ impl FileDlg {
         }
     }
 
-    pub fn dialog_type(&self) -> FileDialogType {
-        unsafe {
-            let val = get_str_attribute_slice(self.handle(), "DIALOGTYPE\0");
-            FileDialogType::from_str(&*val)
-        }
-    }
+    attr_methods!(pub dialog_type, try_dialog_type: FileDialogType = DIALOGTYPE);
 
     pub fn set_dialog_type(&self, ty: FileDialogType) {
-        set_str_attribute(self.handle(), "DIALOGTYPE\0", ty.to_str());
+        DIALOGTYPE.set_handle(self.handle(), &ty);
     }
 
     pub fn directory(&self) -> String {
@@ -156,22 +158,17 @@ impl FileDlg {
 
     /// Gets the index of the filter to use. It returns the selection made by the user.
     pub fn filter_used(&self) -> u32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "FILTERUSED\0");
-            s.parse::<u32>().expect("could not convert FILTERUSED to an integer") - 1
-        }
+        self.try_filter_used().expect("could not convert FILTERUSED to an integer")
     }
 
-    pub fn multiple_files(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "MULTIPLEFILES\0") == "YES"
-        }
+    /// Like `filter_used`, but returns an error instead of panicking if the value is invalid.
+    pub fn try_filter_used(&self) -> Result<u32, Error> {
+        get_parsed_attribute(self.handle(), "FILTERUSED\0", |s| {
+            s.parse::<u32>().ok().and_then(|i| i.checked_sub(1))
+        })
     }
 
-    pub fn set_multiple_files(&self, multiple: bool) -> &Self {
-        set_str_attribute(self.handle(), "MULTIPLEFILES\0", if multiple { "YES\0" } else { "NO\0" });
-        self
-    }
+    attr_methods!(pub multiple_files, try_multiple_files, set_multiple_files: bool = attr::MULTIPLEFILES);
 
     pub fn value_single(&self) -> Option<PathBuf> {
         assert!(!self.multiple_files());
@@ -210,7 +207,7 @@ impl FileDlg {
     }
 }
 
-impl_control_traits!(FileDlg);
+impl_control_traits!(FileDlg, "filedlg");
 
 impl Popup for FileDlg {}
 
@@ -36,7 +36,7 @@ impl Frame {
     }
 }
 
-impl_control_traits!(Frame);
+impl_control_traits!(Frame, "frame");
 
 impl Container for Frame {}
 impl NonDialogContainer for Frame {}
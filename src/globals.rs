@@ -0,0 +1,148 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+//! Global attributes, which describe the screen, the system, and IUP itself.
+//!
+//! All of these functions must be called on the UI thread.
+
+use std::ffi::CStr;
+use std::ptr;
+use iup_sys::*;
+use super::attr::Attr;
+use super::attributes::{get_parsed_attribute, get_str_attribute};
+use super::error::Error;
+
+const SCREENSIZE: Attr<(u32, u32)> = Attr::new("SCREENSIZE\0");
+const FULLSIZE: Attr<(u32, u32)> = Attr::new("FULLSIZE\0");
+const SCREENDPI: Attr<f64> = Attr::new("SCREENDPI\0");
+
+/// The position and size of a monitor, in pixels. The primary monitor's top left corner is at
+/// (0, 0), so other monitors can have negative coordinates.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Monitor {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Monitor {
+    // Parses the "x y width height" format of MONITORSINFO and VIRTUALSCREEN.
+    fn from_str(s: &str) -> Option<Monitor> {
+        let mut parts = s.split_whitespace();
+        let monitor = Monitor {
+            x: parts.next()?.parse().ok()?,
+            y: parts.next()?.parse().ok()?,
+            width: parts.next()?.parse().ok()?,
+            height: parts.next()?.parse().ok()?,
+        };
+        if parts.next().is_some() { None } else { Some(monitor) }
+    }
+}
+
+fn get_global(name: &str) -> String {
+    ::iup_open();
+    get_str_attribute(ptr::null_mut(), name)
+}
+
+/// The size of the screen in pixels, not counting the taskbar and other system bars.
+pub fn screen_size() -> (u32, u32) {
+    try_screen_size().unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Like `screen_size`, but returns an error instead of panicking if the value is invalid.
+pub fn try_screen_size() -> Result<(u32, u32), Error> {
+    ::iup_open();
+    SCREENSIZE.try_get_handle(ptr::null_mut())
+}
+
+/// The full size of the screen in pixels, including the taskbar and other system bars.
+pub fn full_size() -> (u32, u32) {
+    try_full_size().unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Like `full_size`, but returns an error instead of panicking if the value is invalid.
+pub fn try_full_size() -> Result<(u32, u32), Error> {
+    ::iup_open();
+    FULLSIZE.try_get_handle(ptr::null_mut())
+}
+
+/// The resolution of the screen in dots per inch.
+pub fn screen_dpi() -> f64 {
+    try_screen_dpi().unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Like `screen_dpi`, but returns an error instead of panicking if the value is invalid.
+pub fn try_screen_dpi() -> Result<f64, Error> {
+    ::iup_open();
+    SCREENDPI.try_get_handle(ptr::null_mut())
+}
+
+/// The position and size of every monitor. The list is empty on drivers that don't support
+/// MONITORSINFO, which is everything but Windows and GTK.
+pub fn monitors_info() -> Vec<Monitor> {
+    try_monitors_info().unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Like `monitors_info`, but returns an error instead of panicking if the value is invalid.
+pub fn try_monitors_info() -> Result<Vec<Monitor>, Error> {
+    ::iup_open();
+    get_parsed_attribute(ptr::null_mut(), "MONITORSINFO\0", |s| {
+        s.lines().filter(|line| !line.trim().is_empty()).map(Monitor::from_str).collect()
+    })
+}
+
+/// The position and size of the rectangle that contains every monitor.
+pub fn virtual_screen() -> Monitor {
+    try_virtual_screen().unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Like `virtual_screen`, but returns an error instead of panicking if the value is invalid.
+pub fn try_virtual_screen() -> Result<Monitor, Error> {
+    ::iup_open();
+    get_parsed_attribute(ptr::null_mut(), "VIRTUALSCREEN\0", Monitor::from_str)
+}
+
+/// The name of the operating system, such as "Win10", "Linux", or "MacOS".
+pub fn system() -> String {
+    get_global("SYSTEM\0")
+}
+
+/// The version of the operating system.
+pub fn system_version() -> String {
+    get_global("SYSTEMVERSION\0")
+}
+
+/// The language of the operating system, such as "en_US" or "1033".
+pub fn system_language() -> String {
+    get_global("SYSTEMLANGUAGE\0")
+}
+
+/// The font that controls use when FONT isn't set, such as "Sans, 10".
+pub fn default_font() -> String {
+    get_global("DEFAULTFONT\0")
+}
+
+/// The name of the driver IUP is using: "Win32", "GTK", or "Motif".
+pub fn driver() -> String {
+    get_global("DRIVER\0")
+}
+
+/// The version of the IUP library being used, such as "3.19.1".
+pub fn iup_version() -> String {
+    unsafe { CStr::from_ptr(IupVersion()).to_string_lossy().into_owned() }
+}
+
+/// The release date of the IUP library being used, such as "2016/07/21".
+pub fn iup_version_date() -> String {
+    unsafe { CStr::from_ptr(IupVersionDate()).to_string_lossy().into_owned() }
+}
+
+/// The version of the IUP library being used as a number, such as 319001 for 3.19.1.
+pub fn iup_version_number() -> i32 {
+    unsafe { IupVersionNumber() as i32 }
+}
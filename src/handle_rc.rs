@@ -89,7 +89,27 @@ thread_local!(
     static EXISTING_HANDLES: RefCell<HashMap<*mut Ihandle, Weak<HandleBox>>> = RefCell::new(HashMap::new())
 );
 
+// Flags shared by the `WeakControl`s referring to a control, which are cleared when it is
+// destroyed. Unlike the `Weak` in `EXISTING_HANDLES`, a flag stays set while the control exists
+// without any wrappers, such as when its wrappers are dropped after it is added to a container.
+thread_local!(
+    static ALIVE_FLAGS: RefCell<HashMap<*mut Ihandle, Rc<Cell<bool>>>> = RefCell::new(HashMap::new())
+);
+
+// Returns the flag for a control that has a `HandleRc`, so it has an LDESTROY_CB set.
+pub fn get_alive_flag(ih: *mut Ihandle) -> Rc<Cell<bool>> {
+    ALIVE_FLAGS.with(|map| {
+        map.borrow_mut().entry(ih).or_insert_with(|| Rc::new(Cell::new(true))).clone()
+    })
+}
+
 pub fn handle_rc_destroy_cb(ih: *mut Ihandle) {
+    // This is done first so that nothing can wrap the control again while it is being destroyed.
+    ALIVE_FLAGS.with(|map| {
+        if let Some(flag) = map.borrow_mut().remove(&ih) {
+            flag.set(false);
+        }
+    });
     EXISTING_HANDLES.with(|cell| {
         let mut map = cell.borrow_mut();
         if let Some(weak) = map.remove(&ih) {
@@ -0,0 +1,112 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::cell::Cell;
+use std::mem;
+use std::ptr;
+use libc::{c_char, c_int};
+use iup_sys::*;
+use super::callbacks::{with_callbacks, CallbackRegistry, Token};
+
+// iup-sys declares the callback as non-nullable, but passing null is how IDLE_ACTION is removed.
+extern {
+    #[link_name = "IupSetFunction"]
+    fn set_function(name: *const c_char, func: Option<Icallback>) -> Option<Icallback>;
+}
+
+callback_token!(IdleCallbackToken);
+
+// IDLE_ACTION is a global callback, but `CallbackRegistry` stores callbacks per control, so they
+// are stored under an element that is never destroyed.
+thread_local!(static IDLE_HANDLE: Cell<*mut Ihandle> = Cell::new(ptr::null_mut()));
+
+thread_local!(
+    static IDLE_CALLBACKS: CallbackRegistry<FnMut() -> bool, IdleCallbackToken> =
+        CallbackRegistry::new("IDLE_ACTION\0", unsafe {
+            mem::transmute::<_, Icallback>(idle_action as usize)
+        })
+);
+
+fn get_idle_handle() -> *mut Ihandle {
+    IDLE_HANDLE.with(|cell| {
+        if cell.get().is_null() {
+            cell.set(unsafe { IupUser() });
+        }
+        cell.get()
+    })
+}
+
+fn set_idle_action(enabled: bool) {
+    let func = if enabled {
+        Some(unsafe { mem::transmute::<_, Icallback>(idle_action as usize) })
+    } else {
+        None
+    };
+    unsafe {
+        set_function("IDLE_ACTION\0".as_ptr() as *const c_char, func);
+    }
+}
+
+extern fn idle_action() -> c_int {
+    let ih = get_idle_handle();
+    let mut finished = vec![];
+    let result = with_callbacks(ih, &IDLE_CALLBACKS, |cbs| {
+        for &(id, ref cb) in cbs {
+            if !(&mut *cb.borrow_mut())() {
+                finished.push(id);
+            }
+        }
+        IUP_DEFAULT
+    });
+    for id in finished {
+        // The function could have removed itself before returning false.
+        if IDLE_CALLBACKS.with(|reg| reg.has_callback(ih, id)) {
+            remove_idle(IdleCallbackToken(Token { id: id, ih: ih }));
+        }
+    }
+    result
+}
+
+/// Adds a function to be called repeatedly whenever there are no events to process. It is kept
+/// until it returns false or `remove_idle` is called with the returned token.
+///
+/// Each function should do a small amount of work, such as loading part of a file, so that events
+/// are not delayed. While any functions are added, the main loop uses the CPU continuously
+/// instead of waiting for events.
+///
+/// A panic in the function is propagated out of the main loop, the same as for panics in
+/// callbacks.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use clear_coat::*;
+/// let mut lines_loaded = 0;
+/// on_idle(move || {
+///     lines_loaded += 100;
+///     lines_loaded < 10000
+/// });
+/// ```
+pub fn on_idle<F: FnMut() -> bool + 'static>(f: F) -> IdleCallbackToken {
+    ::iup_open();
+    let token = IDLE_CALLBACKS.with(|reg| reg.add_callback(get_idle_handle(), f));
+    set_idle_action(true);
+    token
+}
+
+/// Removes a function added with `on_idle`.
+///
+/// Panics if the function was already removed, including by returning false.
+pub fn remove_idle(token: IdleCallbackToken) {
+    ::check_thread();
+    let ih = get_idle_handle();
+    IDLE_CALLBACKS.with(|reg| reg.remove_callback(ih, token));
+    let is_empty = IDLE_CALLBACKS.with(|reg| !reg.has_callbacks(ih));
+    if is_empty {
+        set_idle_action(false);
+    }
+}
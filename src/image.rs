@@ -0,0 +1,253 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::slice;
+#[cfg(feature = "image-files")]
+use std::path::Path;
+#[cfg(feature = "image-files")]
+use decoders::{self, DynamicImage, ImageError, ImageReader};
+use super::control_prelude::*;
+use super::attributes::get_attribute_handle;
+use super::extra_refs::{add_extra_ref, remove_extra_ref, ExtraRefKey};
+
+/// How an image's pixels are stored.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ImageFormat {
+    /// One byte per pixel, which is an index into the image's palette.
+    Indexed,
+    /// Three bytes per pixel: red, green, and blue.
+    Rgb,
+    /// Four bytes per pixel: red, green, blue, and alpha.
+    Rgba,
+}
+
+impl ImageFormat {
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            ImageFormat::Indexed => 1,
+            ImageFormat::Rgb => 3,
+            ImageFormat::Rgba => 4,
+        }
+    }
+}
+
+/// An image that can be shown on controls such as `Button` and `Label`, used as a dialog's icon,
+/// or copied to the clipboard.
+///
+/// Pixels are given in rows from top to bottom, and IUP copies them when the image is created.
+/// Like controls, an image is destroyed when the last reference to it is dropped, and controls
+/// showing it keep a reference.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use clear_coat::*;
+/// # use clear_coat::common_attrs_cbs::*;
+/// // A 2x2 checkerboard with a transparent background.
+/// let palette = [None, Some((0, 0, 0))];
+/// let image = Image::from_indexed(2, 2, &[1, 0, 0, 1], &palette);
+/// let button = Button::new();
+/// button.set_image(Some(&image));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Image(HandleRc);
+
+impl Image {
+    /// Creates an image with a palette of up to 256 colors. Each pixel is one byte that is an
+    /// index into `palette`, and a `None` entry in the palette is transparent.
+    ///
+    /// Panics if `pixels` is not `width * height` bytes long or if `palette` has more than 256
+    /// colors.
+    pub fn from_indexed(width: u32, height: u32, pixels: &[u8], palette: &[Option<(u8, u8, u8)>])
+                        -> Image {
+        assert!(palette.len() <= 256, "palette has more than 256 colors");
+        let image = Image::create(width, height, pixels, ImageFormat::Indexed, IupImage);
+        for (i, &color) in palette.iter().enumerate() {
+            image.set_palette_color(i as u8, color);
+        }
+        image
+    }
+
+    /// Creates an image from pixels with 3 bytes each (red, green, and blue).
+    ///
+    /// Panics if `pixels` is not `width * height * 3` bytes long.
+    pub fn from_rgb(width: u32, height: u32, pixels: &[u8]) -> Image {
+        Image::create(width, height, pixels, ImageFormat::Rgb, IupImageRGB)
+    }
+
+    /// Creates an image from pixels with 4 bytes each (red, green, blue, and alpha).
+    ///
+    /// Panics if `pixels` is not `width * height * 4` bytes long.
+    pub fn from_rgba(width: u32, height: u32, pixels: &[u8]) -> Image {
+        Image::create(width, height, pixels, ImageFormat::Rgba, IupImageRGBA)
+    }
+
+    fn create(width: u32, height: u32, pixels: &[u8], format: ImageFormat,
+              new: unsafe extern fn(c_int, c_int, *const u8) -> *mut Ihandle) -> Image {
+        assert_eq!(pixels.len(), width as usize * height as usize * format.bytes_per_pixel(),
+                   "wrong number of bytes for image size");
+        unsafe {
+            ::iup_open();
+            let ih = new(width as c_int, height as c_int, pixels.as_ptr());
+            Image(HandleRc::new(ih))
+        }
+    }
+
+    attr_methods!(pub width, try_width: u32 = attr::WIDTH);
+    attr_methods!(pub height, try_height: u32 = attr::HEIGHT);
+
+    pub fn format(&self) -> ImageFormat {
+        match get_parsed_attribute(self.handle(), "BPP\0", |s| s.parse::<u32>().ok()) {
+            Ok(8) => ImageFormat::Indexed,
+            Ok(24) => ImageFormat::Rgb,
+            _ => ImageFormat::Rgba,
+        }
+    }
+
+    /// Returns a copy of the image's pixels, in the same format they were given in.
+    pub fn pixels(&self) -> Vec<u8> {
+        let len = self.width() as usize * self.height() as usize
+                  * self.format().bytes_per_pixel();
+        // WID is the image's own copy of the pixels.
+        let data = get_attribute_ptr(self.handle(), "WID\0");
+        if data.is_null() {
+            return vec![];
+        }
+        unsafe { slice::from_raw_parts(data as *const u8, len) }.to_owned()
+    }
+
+    /// Returns the color of an entry in the palette of an indexed image, or `None` if it is
+    /// transparent or not set.
+    pub fn palette_color(&self, index: u8) -> Option<(u8, u8, u8)> {
+        let value = get_str_attribute(self.handle(), &format!("{}\0", index));
+        let mut parts = value.split_whitespace().map(|part| part.parse::<u8>());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(r)), Some(Ok(g)), Some(Ok(b)), None) => Some((r, g, b)),
+            _ => None,
+        }
+    }
+
+    /// Sets an entry in the palette of an indexed image. `None` makes it transparent.
+    pub fn set_palette_color(&self, index: u8, color: Option<(u8, u8, u8)>) -> &Self {
+        let value = match color {
+            Some((r, g, b)) => format!("{} {} {}", r, g, b),
+            None => "BGCOLOR".to_owned(),
+        };
+        set_str_attribute(self.handle(), &format!("{}\0", index), &value);
+        self
+    }
+
+    /// Returns the hotspot: the point in the image that is the mouse position when it is used as
+    /// a cursor. The default is (0, 0), the top-left corner.
+    pub fn hotspot(&self) -> (u32, u32) {
+        self.try_hotspot().expect("could not convert HOTSPOT to two integers")
+    }
+
+    pub fn try_hotspot(&self) -> Result<(u32, u32), Error> {
+        get_parsed_attribute(self.handle(), "HOTSPOT\0", |s| {
+            if s.is_empty() {
+                return Some((0, 0));
+            }
+            let mut parts = s.split(':').map(|part| part.parse::<u32>());
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => Some((x, y)),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn set_hotspot(&self, x: u32, y: u32) -> &Self {
+        set_str_attribute(self.handle(), "HOTSPOT\0", &format!("{}:{}", x, y));
+        self
+    }
+
+    pub(crate) fn handle_rc(&self) -> HandleRc {
+        self.0.clone()
+    }
+}
+
+// Decoding needs the "image-files" feature. Every file is converted to RGBA.
+#[cfg(feature = "image-files")]
+impl Image {
+    /// Decodes a PNG file.
+    pub fn from_png_bytes(bytes: &[u8]) -> Result<Image, Error> {
+        Image::decode(bytes, decoders::ImageFormat::Png)
+    }
+
+    /// Decodes a BMP file.
+    pub fn from_bmp_bytes(bytes: &[u8]) -> Result<Image, Error> {
+        Image::decode(bytes, decoders::ImageFormat::Bmp)
+    }
+
+    /// Decodes an ICO file. If it has more than one size, the largest is used.
+    pub fn from_ico_bytes(bytes: &[u8]) -> Result<Image, Error> {
+        Image::decode(bytes, decoders::ImageFormat::Ico)
+    }
+
+    /// Reads a PNG, BMP, or ICO file. The format is found from the file's contents, or from its
+    /// extension if the contents aren't recognized.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Image, Error> {
+        let to_error = |err: ::std::io::Error| Error::ImageFile { message: err.to_string() };
+        let reader = ImageReader::open(path).map_err(&to_error)?
+                                            .with_guessed_format().map_err(&to_error)?;
+        reader.decode().map(Image::from_decoded).map_err(image_file_error)
+    }
+
+    fn decode(bytes: &[u8], format: decoders::ImageFormat) -> Result<Image, Error> {
+        decoders::load_from_memory_with_format(bytes, format).map(Image::from_decoded)
+                                                             .map_err(image_file_error)
+    }
+
+    fn from_decoded(decoded: DynamicImage) -> Image {
+        let rgba = decoded.into_rgba8();
+        Image::from_rgba(rgba.width(), rgba.height(), &rgba)
+    }
+}
+
+#[cfg(feature = "image-files")]
+fn image_file_error(err: ImageError) -> Error {
+    Error::ImageFile { message: err.to_string() }
+}
+
+impl_control_traits!(Image, "image", "imagergb", "imagergba");
+
+// Sets an attribute that refers to an image by name, such as IMAGE, keeping a reference to the
+// image under `key` until the attribute is changed or the control is destroyed. `None` resets the
+// attribute.
+pub(crate) fn set_image_attribute(ih: *mut Ihandle, name: &str, key: ExtraRefKey,
+                                  image: Option<&Image>) {
+    set_image_attribute_with_owner(ih, name, ih, key, image)
+}
+
+// The same as `set_image_attribute`, except that `owner` keeps the reference instead of `ih`.
+pub(crate) fn set_image_attribute_with_owner(ih: *mut Ihandle, name: &str, owner: *mut Ihandle,
+                                             key: ExtraRefKey, image: Option<&Image>) {
+    match image {
+        // This gives the image a global name if it doesn't have one.
+        Some(image) => unsafe { set_attribute_handle(ih, name, image.handle()); },
+        None => reset_attribute(ih, name),
+    }
+    replace_image_ref(owner, key, image);
+}
+
+// Replaces the image kept under `key` with `image`. This must be called after the attribute that
+// referred to the old image is changed, since releasing the old image may destroy it.
+pub(crate) fn replace_image_ref(ih: *mut Ihandle, key: ExtraRefKey, image: Option<&Image>) {
+    remove_extra_ref(ih, key);
+    if let Some(image) = image {
+        add_extra_ref(ih, key, image.handle_rc());
+    }
+}
+
+pub(crate) fn get_image_attribute(ih: *mut Ihandle, name: &str) -> Option<Image> {
+    let image_ih = get_attribute_handle(ih, name);
+    if image_ih.is_null() {
+        None
+    } else {
+        unsafe { Image::try_from_handle(image_ih) }
+    }
+}
@@ -0,0 +1,319 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::fmt;
+use std::str::FromStr;
+use super::Error;
+
+// The modifier flags IUP adds to key codes (iup_isShiftXkey and the others in iupkey.h).
+const SHIFT_FLAG: u32 = 0x1000_0000;
+const CTRL_FLAG: u32 = 0x2000_0000;
+const ALT_FLAG: u32 = 0x4000_0000;
+const SYS_FLAG: u32 = 0x8000_0000;
+const BASE_MASK: u32 = 0x0FFF_FFFF;
+
+/// A key on the keyboard, without modifiers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    /// A key that types a character, other than the ones below. Letters are always uppercase,
+    /// and `Key::shift` says whether Shift was held.
+    Char(char),
+    Space,
+    Backspace,
+    Tab,
+    Enter,
+    Escape,
+    Home,
+    End,
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    Pause,
+    Print,
+    Menu,
+    /// The 5 key on the numeric keypad when Num Lock is off.
+    Middle,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    /// A function key, from 1 to 20.
+    F(u8),
+    LeftShift,
+    RightShift,
+    LeftCtrl,
+    RightCtrl,
+    LeftAlt,
+    RightAlt,
+    LeftSys,
+    RightSys,
+    /// A key code that isn't known, without the modifier flags.
+    Unknown(u32),
+}
+
+// The key codes from iupkey.h for keys that don't type a character.
+const NAMED_KEYS: &'static [(u32, KeyCode, &'static str)] = &[
+    (b' ' as u32, KeyCode::Space, "Space"),
+    (8, KeyCode::Backspace, "Backspace"),
+    (b'\t' as u32, KeyCode::Tab, "Tab"),
+    (b'\r' as u32, KeyCode::Enter, "Enter"),
+    (0xFF1B, KeyCode::Escape, "Esc"),
+    (0xFF50, KeyCode::Home, "Home"),
+    (0xFF57, KeyCode::End, "End"),
+    (0xFF51, KeyCode::Left, "Left"),
+    (0xFF53, KeyCode::Right, "Right"),
+    (0xFF52, KeyCode::Up, "Up"),
+    (0xFF54, KeyCode::Down, "Down"),
+    (0xFF55, KeyCode::PageUp, "PageUp"),
+    (0xFF56, KeyCode::PageDown, "PageDown"),
+    (0xFF63, KeyCode::Insert, "Insert"),
+    (0xFFFF, KeyCode::Delete, "Delete"),
+    (0xFF13, KeyCode::Pause, "Pause"),
+    (0xFF61, KeyCode::Print, "Print"),
+    (0xFF67, KeyCode::Menu, "Menu"),
+    (0xFF0B, KeyCode::Middle, "Middle"),
+    (0xFFE5, KeyCode::CapsLock, "CapsLock"),
+    (0xFF7F, KeyCode::NumLock, "NumLock"),
+    (0xFF14, KeyCode::ScrollLock, "ScrollLock"),
+    (0xFFE1, KeyCode::LeftShift, "LeftShift"),
+    (0xFFE2, KeyCode::RightShift, "RightShift"),
+    (0xFFE3, KeyCode::LeftCtrl, "LeftCtrl"),
+    (0xFFE4, KeyCode::RightCtrl, "RightCtrl"),
+    (0xFFE9, KeyCode::LeftAlt, "LeftAlt"),
+    (0xFFEA, KeyCode::RightAlt, "RightAlt"),
+    (0xFFEB, KeyCode::LeftSys, "LeftSys"),
+    (0xFFEC, KeyCode::RightSys, "RightSys"),
+];
+
+// K_F1 to K_F20 are consecutive.
+const F1_CODE: u32 = 0xFFBE;
+
+// Other names accepted when parsing.
+const KEY_ALIASES: &'static [(&'static str, KeyCode)] = &[
+    ("Escape", KeyCode::Escape),
+    ("Return", KeyCode::Enter),
+    ("Del", KeyCode::Delete),
+    ("Ins", KeyCode::Insert),
+    ("PgUp", KeyCode::PageUp),
+    ("PgDn", KeyCode::PageDown),
+];
+
+impl KeyCode {
+    fn from_code(code: u32) -> KeyCode {
+        if let Some(&(_, key, _)) = NAMED_KEYS.iter().find(|&&(c, _, _)| c == code) {
+            return key;
+        }
+        if code >= F1_CODE && code < F1_CODE + 20 {
+            return KeyCode::F((code - F1_CODE + 1) as u8);
+        }
+        // Key codes for characters are their Unicode values, which end before the codes above.
+        match ::std::char::from_u32(code) {
+            Some(c) if code < 0xFF00 && !c.is_control() =>
+                KeyCode::Char(c.to_uppercase().next().unwrap_or(c)),
+            _ => KeyCode::Unknown(code),
+        }
+    }
+
+    fn to_code(self) -> u32 {
+        match self {
+            KeyCode::Char(c) => c as u32,
+            KeyCode::F(n) => F1_CODE + n as u32 - 1,
+            KeyCode::Unknown(code) => code,
+            key => NAMED_KEYS.iter().find(|&&(_, k, _)| k == key).map(|&(c, _, _)| c).unwrap(),
+        }
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        NAMED_KEYS.iter().find(|&&(_, k, _)| k == *self).map(|&(_, _, name)| name)
+    }
+}
+
+/// A key along with the modifiers held while it was pressed, as passed to `k_any_event`.
+///
+/// A key can be written as text such as "Ctrl+Shift+S", "Alt+F4", or "Delete". Modifiers come
+/// first in the order Ctrl, Alt, Shift, Sys, and are separated by `+`. Parsing ignores case and
+/// order and also accepts "Control" for Ctrl.
+///
+/// Letters are always uppercase. Typing a capital letter, with Shift or Caps Lock, gives a key
+/// with `shift` set, so "Shift+A" is a capital A and "A" is a lowercase a. For other characters,
+/// the character typed is the key, so with a US keyboard layout, Shift+1 is "!".
+///
+/// # Examples
+///
+/// ```
+/// # use clear_coat::*;
+/// let key: Key = "ctrl+shift+s".parse().unwrap();
+/// assert_eq!(key, Key::new(KeyCode::Char('S')).ctrl().shift());
+/// assert_eq!(key.to_string(), "Ctrl+Shift+S");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub sys: bool,
+}
+
+impl Key {
+    /// Creates a key without any modifiers.
+    pub fn new(code: KeyCode) -> Key {
+        Key { code: code, shift: false, ctrl: false, alt: false, sys: false }
+    }
+
+    pub fn shift(mut self) -> Key {
+        self.shift = true;
+        self
+    }
+
+    pub fn ctrl(mut self) -> Key {
+        self.ctrl = true;
+        self
+    }
+
+    pub fn alt(mut self) -> Key {
+        self.alt = true;
+        self
+    }
+
+    pub fn sys(mut self) -> Key {
+        self.sys = true;
+        self
+    }
+
+    /// Decodes a key code passed to IUP's K_ANY or KEYPRESS_CB callbacks.
+    pub fn from_iup_code(code: u32) -> Key {
+        let base = code & BASE_MASK;
+        let mut key = Key {
+            code: KeyCode::from_code(base),
+            shift: code & SHIFT_FLAG != 0,
+            ctrl: code & CTRL_FLAG != 0,
+            alt: code & ALT_FLAG != 0,
+            sys: code & SYS_FLAG != 0,
+        };
+        // Without another modifier, IUP reports Shift with a letter by making it uppercase
+        // instead of with the flag. With one, letters are always uppercase.
+        let is_capital = ::std::char::from_u32(base).map_or(false, |c| c.is_uppercase());
+        if is_capital && !(key.ctrl || key.alt || key.sys) {
+            key.shift = true;
+        }
+        key
+    }
+
+    /// Encodes this key the way IUP passes it to callbacks.
+    pub fn to_iup_code(&self) -> u32 {
+        let mut code = self.code.to_code();
+        let mut shift = self.shift;
+        if let KeyCode::Char(c) = self.code {
+            if c.is_uppercase() && !(self.ctrl || self.alt || self.sys) {
+                if !shift {
+                    code = c.to_lowercase().next().unwrap_or(c) as u32;
+                }
+                shift = false;
+            }
+        }
+        if shift { code |= SHIFT_FLAG; }
+        if self.ctrl { code |= CTRL_FLAG; }
+        if self.alt { code |= ALT_FLAG; }
+        if self.sys { code |= SYS_FLAG; }
+        code
+    }
+
+    /// Returns the character this key types, if it types one and is pressed without Ctrl, Alt, or
+    /// Sys.
+    pub fn to_char(&self) -> Option<char> {
+        if self.ctrl || self.alt || self.sys {
+            return None;
+        }
+        match self.code {
+            KeyCode::Char(c) if c.is_uppercase() && !self.shift => c.to_lowercase().next(),
+            KeyCode::Char(c) => Some(c),
+            KeyCode::Space => Some(' '),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.ctrl { f.write_str("Ctrl+")?; }
+        if self.alt { f.write_str("Alt+")?; }
+        if self.shift { f.write_str("Shift+")?; }
+        if self.sys { f.write_str("Sys+")?; }
+        match self.code {
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Unknown(code) => write!(f, "{:#X}", code),
+            key => f.write_str(key.name().unwrap()),
+        }
+    }
+}
+
+fn parse_key_code(s: &str) -> Option<KeyCode> {
+    let mut chars = s.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return match c {
+            ' ' => Some(KeyCode::Space),
+            c if c.is_control() => None,
+            c => Some(KeyCode::Char(c.to_uppercase().next().unwrap_or(c))),
+        };
+    }
+    let lower = s.to_lowercase();
+    let named = NAMED_KEYS.iter().find(|&&(_, _, name)| name.to_lowercase() == lower);
+    if let Some(&(_, key, _)) = named {
+        return Some(key);
+    }
+    if let Some(&(_, key)) = KEY_ALIASES.iter().find(|&&(name, _)| name.to_lowercase() == lower) {
+        return Some(key);
+    }
+    if lower.starts_with('f') {
+        if let Ok(n) = lower[1..].parse::<u8>() {
+            if n >= 1 && n <= 20 {
+                return Some(KeyCode::F(n));
+            }
+        }
+    }
+    if lower.starts_with("0x") {
+        if let Ok(code) = u32::from_str_radix(&lower[2..], 16) {
+            return Some(KeyCode::from_code(code & BASE_MASK));
+        }
+    }
+    None
+}
+
+impl FromStr for Key {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Key, Error> {
+        let invalid = || Error::InvalidKey { text: s.to_owned() };
+        let s = s.trim();
+        // The key itself can be "+", as in "Ctrl++".
+        let (modifiers, key) = if s.ends_with('+') {
+            (s[..s.len() - 1].trim_end_matches('+'), "+")
+        } else {
+            match s.rfind('+') {
+                Some(i) => (&s[..i], &s[i + 1..]),
+                None => ("", s),
+            }
+        };
+        let mut parsed = Key::new(parse_key_code(key.trim()).ok_or_else(&invalid)?);
+        for modifier in modifiers.split('+').map(|m| m.trim()).filter(|m| !m.is_empty()) {
+            match &*modifier.to_lowercase() {
+                "ctrl" | "control" => parsed.ctrl = true,
+                "alt" => parsed.alt = true,
+                "shift" => parsed.shift = true,
+                "sys" => parsed.sys = true,
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(parsed)
+    }
+}
@@ -33,10 +33,14 @@ impl Label {
 
     /// Gets the horizontal alignment of the contents of the label.
     pub fn halignment(&self) -> ::HAlignment {
-        unsafe {
-            let slice = get_str_attribute_slice(self.handle(), "ALIGNMENT\0");
-            ::HAlignment::from_str(slice.as_bytes().split(|c| *c == b':').next().unwrap())
-        }
+        self.try_halignment().expect("unknown HAlignment")
+    }
+
+    /// Like `halignment`, but returns an error instead of panicking if the value is unknown.
+    pub fn try_halignment(&self) -> Result<::HAlignment, Error> {
+        get_parsed_attribute(self.handle(), "ALIGNMENT\0", |s| {
+            ::HAlignment::from_str(s.as_bytes().split(|c| *c == b':').next().unwrap())
+        })
     }
 
     /// Sets the horizontal alignment of the contents of the label.
@@ -46,15 +50,20 @@ impl Label {
     }
 }
 
-impl_control_traits!(Label);
+impl_control_traits!(Label, "label");
 
 impl ActiveAttribute for Label {}
 impl ExpandAttribute for Label {}
+impl ImageAttribute for Label {}
+impl InactiveImageAttribute for Label {}
 impl MinMaxSizeAttribute for Label {}
 impl TipAttribute for Label {}
 impl TitleAttribute for Label {}
 impl VisibleAttribute for Label {}
 
 impl MenuCommonCallbacks for Label {}
+impl DropFilesCallback for Label {}
+impl DragSource for Label {}
+impl DropTarget for Label {}
 
 impl EnterLeaveWindowCallbacks for Label {}
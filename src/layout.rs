@@ -0,0 +1,130 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+/// Builds a tree of controls, setting attributes and adding event handlers inline.
+///
+/// Each node is a control type, which is created with its `new` function, or a parenthesized
+/// expression, such as `(fill!())` or `(Item::with_title("Open"))`. A node can be followed by
+/// `as name` to bind it to a local variable, which is a clone of the wrapper, and then by a block
+/// containing a comma-separated list of:
+///
+/// - Method calls starting with a dot, such as `.set_title("OK")` or
+///   `.set(attr::EXPAND, Expand::Horizontal)`
+/// - Event handlers, such as `action_event => || println!("clicked")`
+/// - Child nodes, which are appended to the control in order
+///
+/// `layout!` must be used as a statement so that the `as` bindings are in scope after it. Each
+/// child is kept in a binding until it has been appended to its parent, so no control is destroyed
+/// while the tree is being built.
+///
+/// A block with many items can reach the default macro recursion limit, which can be raised with
+/// `#![recursion_limit]`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[macro_use] extern crate clear_coat;
+/// # use clear_coat::*;
+/// # use clear_coat::common_attrs_cbs::*;
+/// # fn main() {
+/// layout!(Dialog as dialog {
+///     .set_title("Sign Up"),
+///     Vbox {
+///         .set_gap(10),
+///         Text as email {
+///             .set_visible_columns(30),
+///         },
+///         Hbox {
+///             (fill!()),
+///             Button as ok {
+///                 .set_title("OK"),
+///                 action_event => || println!("signed up"),
+///             },
+///         },
+///     },
+/// });
+/// email.set_value("me@example.com");
+/// dialog.show_xy(ScreenPosition::Center, ScreenPosition::Center).unwrap();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! layout {
+    // Creates a node and binds it to `$var`.
+    (@node $var:ident; $ty:ident as $bind:ident { $($body:tt)* }) => {
+        let $var = $ty::new();
+        let $bind = $var.clone();
+        layout!(@items $var; $($body)*);
+    };
+    (@node $var:ident; $ty:ident as $bind:ident) => {
+        let $var = $ty::new();
+        let $bind = $var.clone();
+    };
+    (@node $var:ident; $ty:ident { $($body:tt)* }) => {
+        let $var = $ty::new();
+        layout!(@items $var; $($body)*);
+    };
+    (@node $var:ident; $ty:ident) => {
+        let $var = $ty::new();
+    };
+    (@node $var:ident; ($e:expr) as $bind:ident { $($body:tt)* }) => {
+        let $var = $e;
+        let $bind = $var.clone();
+        layout!(@items $var; $($body)*);
+    };
+    (@node $var:ident; ($e:expr) as $bind:ident) => {
+        let $var = $e;
+        let $bind = $var.clone();
+    };
+    (@node $var:ident; ($e:expr) { $($body:tt)* }) => {
+        let $var = $e;
+        layout!(@items $var; $($body)*);
+    };
+    (@node $var:ident; ($e:expr)) => {
+        let $var = $e;
+    };
+
+    // Applies the items in a node's block to the control in `$parent`.
+    (@items $parent:ident;) => {};
+    (@items $parent:ident; . $method:ident ( $($args:tt)* ) , $($rest:tt)*) => {
+        $parent.$method($($args)*);
+        layout!(@items $parent; $($rest)*);
+    };
+    (@items $parent:ident; . $method:ident ( $($args:tt)* )) => {
+        $parent.$method($($args)*);
+    };
+    (@items $parent:ident; $event:ident => $handler:expr , $($rest:tt)*) => {
+        let _ = $parent.$event().add($handler);
+        layout!(@items $parent; $($rest)*);
+    };
+    (@items $parent:ident; $event:ident => $handler:expr) => {
+        let _ = $parent.$event().add($handler);
+    };
+    (@items $parent:ident; $($rest:tt)+) => {
+        layout!(@child $parent; []; $($rest)+);
+    };
+
+    // Collects the tokens of a child node up to the next comma.
+    (@child $parent:ident; [$($node:tt)+]; , $($rest:tt)*) => {
+        layout!(@append $parent; $($node)+);
+        layout!(@items $parent; $($rest)*);
+    };
+    (@child $parent:ident; [$($node:tt)+];) => {
+        layout!(@append $parent; $($node)+);
+    };
+    (@child $parent:ident; [$($node:tt)*]; $next:tt $($rest:tt)*) => {
+        layout!(@child $parent; [$($node)* $next]; $($rest)*);
+    };
+
+    (@append $parent:ident; $($node:tt)+) => {
+        layout!(@node child; $($node)+);
+        $crate::Container::append(&$parent, &child).expect("failed to append a child in layout!");
+    };
+
+    ($($node:tt)+) => {
+        layout!(@node root; $($node)+);
+    };
+}
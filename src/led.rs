@@ -0,0 +1,369 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+//! Loading controls from IUP's LED language, and writing them back out.
+//!
+//! LED describes controls the same way they are created in code, so layouts can be changed
+//! without recompiling:
+//!
+//! ```text
+//! ok = BUTTON("OK", do_nothing)
+//! dlg = DIALOG[TITLE="Hello"](VBOX(LABEL("Hello, world!"), ok))
+//! ```
+//!
+//! Every element given a name in LED can be gotten from the `Led` that `load` returns, using the
+//! wrapper for its type:
+//!
+//! ```no_run
+//! # use clear_coat::*;
+//! # use clear_coat::led;
+//! let led = led::load_buffer(r#"dlg = DIALOG[TITLE="Hello"](LABEL("Hello, world!"))"#).unwrap();
+//! let dialog = led.get::<Dialog>("dlg").unwrap();
+//! ```
+//!
+//! Going the other way, `export` writes any control and its children as LED, and `LayoutNode`
+//! is the same information as a tree that can be printed or compared.
+
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::fmt::{self, Write};
+use std::path::Path;
+use std::slice;
+use super::control_prelude::*;
+use super::AnyControl;
+use super::any_control::wrap_named;
+
+/// The named elements created by loading LED.
+///
+/// The elements are reference counted the same as ones created in code. Elements that haven't
+/// been added to a parent are destroyed when the `Led` and every other wrapper referencing them
+/// are dropped.
+pub struct Led {
+    named: HashMap<String, AnyControl>,
+}
+
+impl Led {
+    /// Returns the element named `name`. Returns an error if no element loaded has the name or
+    /// if it isn't a `T`.
+    pub fn get<T: ControlType>(&self, name: &str) -> Result<T, Error> {
+        let ih = self.named.get(name).map(|c| c.handle()).unwrap_or(ptr::null_mut());
+        wrap_named(ih, name)
+    }
+
+    /// Returns the element named `name` using the wrapper for its class.
+    pub fn get_any(&self, name: &str) -> Option<AnyControl> {
+        self.named.get(name).cloned()
+    }
+
+    /// Returns the names of all the elements loaded, in no particular order.
+    pub fn names(&self) -> Vec<&str> {
+        self.named.keys().map(|s| &s[..]).collect()
+    }
+}
+
+// Returns every name set with `IupSetHandle` and the control it refers to.
+fn get_all_names() -> HashMap<String, *mut Ihandle> {
+    unsafe {
+        let count = IupGetAllNames(ptr::null_mut(), 0);
+        let mut names = vec![ptr::null_mut(); count.max(0) as usize];
+        let count = IupGetAllNames(names.as_mut_ptr(), count);
+        names.truncate(count.max(0) as usize);
+        names.into_iter().filter(|name| !name.is_null()).map(|name| {
+            let ih = IupGetHandle(name);
+            (CStr::from_ptr(name).to_string_lossy().into_owned(), ih)
+        }).collect()
+    }
+}
+
+// IUP doesn't return the elements it loads, but it gives each named one a global name. Any name
+// that is new or refers to a different element after loading was created by the LED.
+fn wrap_loaded<F: FnOnce() -> *mut c_char>(load: F) -> Result<Led, Error> {
+    ::iup_open();
+    let before = get_all_names();
+    let err = load();
+    if !err.is_null() {
+        let message = unsafe { CStr::from_ptr(err).to_string_lossy().into_owned() };
+        return Err(Error::Led { message: message });
+    }
+    let named = get_all_names().into_iter().filter(|&(ref name, ih)| {
+        !ih.is_null() && before.get(name) != Some(&ih)
+    }).map(|(name, ih)| {
+        let control = unsafe { AnyControl::from_handle(ih) };
+        (name, control)
+    }).collect();
+    Ok(Led { named: named })
+}
+
+/// Loads a LED file. A syntax error, or an error opening the file, is returned as `Error::Led`
+/// with the message from IUP.
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Led, Error> {
+    // IUP is set to take UTF-8 file names, so a path that isn't UTF-8 can't be opened.
+    let path = match path.as_ref().to_str() {
+        Some(path) => path,
+        None => return Err(Error::Led { message: "file name is not valid UTF-8".to_owned() }),
+    };
+    let mut path_buf = SmallVec::<[u8; 256]>::new();
+    let c_path = str_to_c_vec(path, &mut path_buf);
+    wrap_loaded(|| unsafe { IupLoad(c_path) })
+}
+
+/// Loads LED from a string. A syntax error is returned as `Error::Led` with the message from
+/// IUP.
+pub fn load_buffer(led: &str) -> Result<Led, Error> {
+    let mut led_buf = SmallVec::<[u8; 256]>::new();
+    let c_led = str_to_c_vec(led, &mut led_buf);
+    wrap_loaded(|| unsafe { IupLoadBuffer(c_led) })
+}
+
+/// A control, the attributes set on it, and its children.
+///
+/// Only attributes that have been set on the control itself are included, not default or
+/// inherited values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutNode {
+    /// The class name, such as "button" or "vbox".
+    pub class_name: String,
+    /// The attribute names and values, sorted by name.
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<LayoutNode>,
+    /// For an image, a copy of its pixels. Empty for other controls.
+    pub pixels: Vec<u8>,
+}
+
+// Returns the names of the attributes stored on the control, without IUP's internal ones. IUP
+// leaves out attributes that are pointers instead of strings, so they are safe to read as strings.
+fn get_attribute_names(ih: *mut Ihandle) -> Vec<String> {
+    unsafe {
+        let count = IupGetAllAttributes(ih, ptr::null_mut(), 0);
+        let mut names = vec![ptr::null_mut(); count.max(0) as usize];
+        let count = IupGetAllAttributes(ih, names.as_mut_ptr(), count);
+        names.truncate(count.max(0) as usize);
+        names.into_iter()
+             .filter(|name| !name.is_null())
+             .map(|name| CStr::from_ptr(name).to_string_lossy().into_owned())
+             .filter(|name| !name.starts_with("_IUP") && !name.starts_with("CLEAR_COAT_"))
+             .filter(|name| !is_callback_name(name))
+             .collect()
+    }
+}
+
+// Callbacks set by name, as LED does, are stored as attributes.
+fn is_callback_name(name: &str) -> bool {
+    name.ends_with("_CB") || name == "ACTION" || name == "IDLE_ACTION" || name.starts_with("K_")
+}
+
+const IMAGE_CLASSES: &[&str] = &["image", "imagergb", "imagergba"];
+
+// The attributes IUP sets from an image's creation parameters. In LED they are parameters.
+const IMAGE_PARAMS: &[&str] = &["WIDTH", "HEIGHT", "BPP"];
+
+fn get_image_pixels(ih: *mut Ihandle) -> Vec<u8> {
+    let get = |name| get_parsed_attribute(ih, name, |s| s.parse::<usize>().ok()).unwrap_or(0);
+    let len = get("WIDTH\0") * get("HEIGHT\0") * get("BPP\0") / 8;
+    let data = get_attribute_ptr(ih, "WID\0");
+    if data.is_null() {
+        return vec![];
+    }
+    unsafe { slice::from_raw_parts(data as *const u8, len) }.to_owned()
+}
+
+// Returns the image with the global name `name`, if there is one.
+fn find_image(name: &str) -> Option<*mut Ihandle> {
+    let mut name_buf = SmallVec::<[u8; 64]>::new();
+    let c_name = str_to_c_vec(name, &mut name_buf);
+    let ih = unsafe { IupGetHandle(c_name) };
+    if !ih.is_null() && IMAGE_CLASSES.contains(&&*::any_control::get_class_name(ih)) {
+        Some(ih)
+    } else {
+        None
+    }
+}
+
+impl LayoutNode {
+    /// Builds the tree for `control` and all of its descendants.
+    pub fn from_control(control: &Control) -> LayoutNode {
+        LayoutNode::from_handle(control.handle())
+    }
+
+    // This doesn't wrap the descendants, so it doesn't have to register them with `HandleRc`.
+    fn from_handle(ih: *mut Ihandle) -> LayoutNode {
+        let class_name = ::any_control::get_class_name(ih);
+        let is_image = IMAGE_CLASSES.contains(&&*class_name);
+        let mut names = get_attribute_names(ih);
+        if is_image {
+            for &name in IMAGE_PARAMS {
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_owned());
+                }
+            }
+        }
+        // An empty value is the same as the attribute not being set.
+        let mut attributes: Vec<_> = names.into_iter().map(|name| {
+            let value = get_str_attribute(ih, &name);
+            (name, value)
+        }).filter(|&(_, ref value)| !value.is_empty()).collect();
+        attributes.sort();
+        let mut children = vec![];
+        unsafe {
+            let mut child = IupGetNextChild(ih, ptr::null_mut());
+            while !child.is_null() {
+                children.push(LayoutNode::from_handle(child));
+                child = IupGetBrother(child);
+            }
+        }
+        LayoutNode {
+            class_name: class_name,
+            attributes: attributes,
+            children: children,
+            pixels: if is_image { get_image_pixels(ih) } else { vec![] },
+        }
+    }
+
+    /// Returns the value of an attribute if it is set on this node.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|&&(ref n, _)| n == name).map(|&(_, ref v)| &v[..])
+    }
+
+    /// Writes this tree as a LED statement that gives the top element the name `name`.
+    ///
+    /// Images that attributes refer to, such as a button's IMAGE, are written as statements
+    /// before it, named `name` followed by `_image` and a number.
+    pub fn to_led(&self, name: &str) -> String {
+        let mut images = vec![];
+        let mut statement = String::new();
+        self.write_led(&mut statement, 0, name, &mut images);
+        let mut led = String::new();
+        for (i, &(_, ref image)) in images.iter().enumerate() {
+            led.push_str(&image.to_led(&image_led_name(name, i)));
+        }
+        writeln!(led, "{} = {}", name, statement).unwrap();
+        led
+    }
+
+    // `images` is the global name and tree of each image referred to so far.
+    fn write_led(&self, led: &mut String, indent: usize, name: &str,
+                 images: &mut Vec<(String, LayoutNode)>) {
+        // Some elements take their title as their first parameter instead of as an attribute.
+        // Callback parameters are left out, since the callbacks aren't exported.
+        let title_param = match &*self.class_name {
+            "button" | "toggle" | "item" | "label" | "submenu" => true,
+            _ => false,
+        };
+        let is_image = IMAGE_CLASSES.contains(&&*self.class_name);
+
+        led.push_str(&self.class_name.to_uppercase());
+        let attributes: Vec<_> = self.attributes.iter()
+            .filter(|&&(ref n, _)| !(title_param && n == "TITLE"))
+            .filter(|&&(ref n, _)| !(is_image && IMAGE_PARAMS.contains(&&**n)))
+            .map(|&(ref n, ref value)| {
+                format!("{}={}", n, quote_led(&led_attribute_value(value, name, images)))
+            })
+            .collect();
+        if !attributes.is_empty() {
+            write!(led, "[{}]", attributes.join(", ")).unwrap();
+        }
+
+        led.push('(');
+        if is_image {
+            // The pixels are written one row per line.
+            let param = |n| self.attribute(n).unwrap_or("0").to_owned();
+            write!(led, "{}, {}", param("WIDTH"), param("HEIGHT")).unwrap();
+            let width = param("WIDTH").parse::<usize>().unwrap_or(0);
+            let bytes_per_pixel = param("BPP").parse::<usize>().unwrap_or(8) / 8;
+            for row in self.pixels.chunks((width * bytes_per_pixel).max(1)) {
+                led.push_str(",\n");
+                for _ in 0..indent + 1 {
+                    led.push_str("  ");
+                }
+                let row: Vec<_> = row.iter().map(|p| p.to_string()).collect();
+                led.push_str(&row.join(", "));
+            }
+        } else if title_param {
+            led.push_str(&quote_led(self.attribute("TITLE").unwrap_or("")));
+        }
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 || title_param {
+                led.push(',');
+            }
+            led.push('\n');
+            for _ in 0..indent + 1 {
+                led.push_str("  ");
+            }
+            child.write_led(led, indent + 1, name, images);
+        }
+        led.push(')');
+    }
+
+    fn write_tree(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+        for _ in 0..indent {
+            f.write_str("  ")?;
+        }
+        f.write_str(&self.class_name)?;
+        if !self.attributes.is_empty() {
+            let attributes: Vec<_> = self.attributes.iter()
+                .map(|&(ref name, ref value)| format!("{}={}", name, quote_led(value)))
+                .collect();
+            write!(f, " [{}]", attributes.join(", "))?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.write_tree(f, indent + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints one line per control, indented under its parent.
+impl fmt::Display for LayoutNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_tree(f, 0)
+    }
+}
+
+// Returns the value to write for an attribute, which is the name the LED gives an image if the
+// value refers to one.
+fn led_attribute_value(value: &str, name: &str, images: &mut Vec<(String, LayoutNode)>)
+                       -> String {
+    let image = match find_image(value) {
+        Some(image) => image,
+        None => return value.to_owned(),
+    };
+    let index = match images.iter().position(|&(ref global, _)| global == value) {
+        Some(index) => index,
+        None => {
+            images.push((value.to_owned(), LayoutNode::from_handle(image)));
+            images.len() - 1
+        },
+    };
+    image_led_name(name, index)
+}
+
+fn image_led_name(name: &str, index: usize) -> String {
+    format!("{}_image{}", name, index + 1)
+}
+
+fn quote_led(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Writes `control` and all of its descendants as a LED statement that gives `control` the name
+/// `name`. Loading the result with `load_buffer` creates a copy of the layout and the images it
+/// uses, without any callbacks.
+pub fn export(control: &Control, name: &str) -> String {
+    LayoutNode::from_control(control).to_led(name)
+}
@@ -14,17 +14,41 @@
 
 extern crate libc;
 extern crate iup_sys;
+#[cfg(windows)]
 extern crate kernel32;
+#[cfg(windows)]
 extern crate winapi;
 extern crate smallvec;
+#[cfg(feature = "image-files")]
+extern crate image as decoders;
 
+// The returned ID must never be 0, as `check_thread` uses 0 to mean that no thread has called
+// into IUP yet.
 #[cfg(windows)]
 fn get_thread_id() -> isize {
     use kernel32::GetCurrentThreadId;
     unsafe { GetCurrentThreadId() as isize }
 }
 
+// `pthread_t` is an integer on Linux and a pointer on macOS and the BSDs, but it is nonzero for
+// every live thread either way.
+#[cfg(unix)]
+fn get_thread_id() -> isize {
+    unsafe { libc::pthread_self() as isize }
+}
+
 macro_rules! impl_control_traits {
+    ($control:path, $($class_name:expr),+) => {
+        impl_control_traits!($control);
+
+        unsafe impl ControlType for $control {
+            const CLASS_NAMES: &'static [&'static str] = &[$($class_name),+];
+
+            unsafe fn from_handle_unchecked(ih: *mut Ihandle) -> Self {
+                $control(HandleRc::new(ih))
+            }
+        }
+    };
     ($control:path) => {
         unsafe impl Control for $control {
             fn handle(&self) -> *mut Ihandle {
@@ -43,6 +67,8 @@ macro_rules! impl_control_traits {
     };
 }
 
+#[macro_use]
+pub mod attr;
 #[macro_use]
 mod callbacks;
 #[macro_use]
@@ -51,11 +77,15 @@ mod containers;
 mod control_prelude;
 
 mod alarm_builder;
+mod any_control;
 mod attributes;
 mod button;
 mod canvas;
+mod clipboard;
 mod frame;
+mod image;
 mod label;
+mod layout;
 mod line_graph;
 mod list;
 mod menu;
@@ -64,27 +94,53 @@ mod tabs;
 mod text;
 mod timer;
 mod toggle;
+mod ui_sender;
 mod dialog;
+mod error;
+pub mod executor;
 mod file_dlg;
+pub mod globals;
+pub mod led;
 mod handle_rc;
+mod weak_control;
+mod property;
+mod key;
+mod shortcuts;
+mod drag_drop;
+pub mod testing;
+mod idle;
 mod extra_refs;
 
-pub use dialog::{Dialog, ShowCallbackToken, ShowState};
+pub use any_control::{AnyControl, ControlType, UnknownControl};
+pub use weak_control::WeakControl;
+pub use property::{Property, PropertyToken};
+pub use key::{Key, KeyCode};
+pub use drag_drop::{DragData, DragSource, DropTarget, DragBeginArgs, DropArgs};
+pub use attr::{Attr, AttrValue};
+pub use dialog::{Dialog, DialogClosed, ShowCallbackToken, ShowState};
+pub use error::Error;
+pub use idle::{on_idle, remove_idle, IdleCallbackToken};
 pub use file_dlg::{FileDlg, FileDialogType, FileExtFilter};
 pub use alarm_builder::{AlarmResult, AlarmBuilder};
-pub use button::Button;
+pub use button::{Button, ButtonClicked};
 pub use canvas::{Canvas};
+pub use clipboard::Clipboard;
 pub use frame::Frame;
+pub use image::{Image, ImageFormat};
 pub use label::Label;
 pub use list::{List, ListActionArgs};
 pub use radio::Radio;
-pub use tabs::{Tabs, TabInfo};
+pub use tabs::{Tabs, TabInfo, TabChangeArgs};
 pub use text::{Text, CaretArgs};
 pub use timer::Timer;
 pub use toggle::{Toggle, ToggleState};
+pub use ui_sender::{UiSender, PostError};
 pub use menu::{Menu, MenuSubitem, Item, Separator, Submenu};
-pub use containers::{Container, NonDialogContainer, Fill, Hbox, Vbox, NumDiv, GridBox};
-pub use callbacks::{CallbackAction, Event, ButtonArgs, CanvasActionArgs};
+pub use containers::{
+    Children, Container, NonDialogContainer, HVBox, Fill, Hbox, Vbox, NumDiv, GridBox,
+};
+pub use callbacks::{CallbackAction, CallbackGuard, Event, ButtonArgs, CanvasActionArgs};
+pub use callbacks::{set_callback_panic_hook, clear_callback_panic_hook};
 
 // With this layout, you can glob import this module's contents but selectively import the
 // above types if you want.
@@ -97,8 +153,11 @@ pub mod common_attrs_cbs {
         CursorAttribute,
         Expand,
         ExpandAttribute,
+        ImageAttribute,
+        InactiveImageAttribute,
         MinMaxSizeAttribute,
         OrientationAttribute,
+        PressedImageAttribute,
         SizeAttribute,
         SingleSizeAttribute,
         ScrollbarAttribute,
@@ -112,6 +171,9 @@ pub mod common_attrs_cbs {
         GetKillFocusCallbacks,
         EnterLeaveWindowCallbacks,
         ButtonCallback,
+        KAnyCallback,
+        DropFilesCallback,
+        DropFilesArgs,
         ValueChangedCallback,
         MotionArgs,
         WheelArgs,
@@ -120,10 +182,13 @@ pub mod common_attrs_cbs {
     };
 }
 
+use std::ffi::CStr;
+use std::panic;
 use std::ptr;
 use std::sync::atomic::{AtomicIsize, Ordering, ATOMIC_ISIZE_INIT};
 use libc::{c_char, c_int};
 use iup_sys::*;
+use smallvec::SmallVec;
 
 // returns false if a panic is pending, but cannot be propagated because the main loop level is too high
 fn propagate_panic() -> bool {
@@ -132,9 +197,7 @@ fn propagate_panic() -> bool {
         return false;
     }
     if let Some(payload) = callbacks::take_panic_payload() {
-        // TODO: once a new nightly is released, use this
-        //::std::panic::propagate(payload);
-        panic!(payload.downcast_ref::<String>().unwrap_or(&String::new()).clone());
+        panic::resume_unwind(payload);
     }
     true
 }
@@ -152,6 +215,8 @@ fn propagate_panic() -> bool {
 /// When the last visible dialog is hidden, the `exit_loop` function is automatically called,
 /// causing the `main_loop` to return. To avoid that, call `set_lock_loop(true)` before hiding the
 /// last dialog.
+///
+/// When the outermost `main_loop` returns, every `UiSender` is closed.
 pub fn main_loop() {
     unsafe {
         iup_open();
@@ -161,6 +226,50 @@ pub fn main_loop() {
         // be 0 in a callback if it is running before IupMainLoop() has been called.)
         if !propagate_panic() { return; }
         IupMainLoop();
+        if main_loop_level() == 0 {
+            ui_sender::close_channel();
+        }
+        propagate_panic();
+    }
+}
+
+/// Processes all pending events and returns without waiting for more. This can be called
+/// repeatedly from an application's own loop instead of calling `main_loop`.
+///
+/// Returns false if `exit_loop` was called or a callback returned `CallbackAction::Close`,
+/// meaning the loop should stop. As with `main_loop`, a panic in a callback is propagated out of
+/// this function.
+pub fn loop_step() -> bool {
+    unsafe {
+        iup_open();
+        if !propagate_panic() { return false; }
+        let result = IupLoopStep();
+        propagate_panic() && result != IUP_CLOSE
+    }
+}
+
+/// Like `loop_step`, but if there are no pending events, waits until there is one before
+/// processing it.
+pub fn loop_step_wait() -> bool {
+    unsafe {
+        iup_open();
+        if !propagate_panic() { return false; }
+        let result = IupLoopStepWait();
+        propagate_panic() && result != IUP_CLOSE
+    }
+}
+
+/// Updates every control whose attributes have changed, processing pending events until there
+/// are none.
+///
+/// Normally, attribute changes are only shown once control returns to the main loop, so this is
+/// useful when doing a long operation in a callback. As with `main_loop`, a panic in a callback
+/// is propagated out of this function.
+pub fn flush() {
+    unsafe {
+        iup_open();
+        if !propagate_panic() { return; }
+        IupFlush();
         propagate_panic();
     }
 }
@@ -202,6 +311,24 @@ pub fn set_lock_loop(lock: bool) {
 }
 
 
+/// Finds the control that was given `name` using `Control::set_global_name`. Returns an error if
+/// no control has the name or if it isn't a `T`.
+pub fn find_global<T: ControlType>(name: &str) -> Result<T, Error> {
+    iup_open();
+    let mut name_buf = SmallVec::<[u8; 64]>::new();
+    let c_name = attributes::str_to_c_vec(name, &mut name_buf);
+    let ih = unsafe { IupGetHandle(c_name) };
+    any_control::wrap_named(ih, name)
+}
+
+/// Removes a name given to a control using `Control::set_global_name`.
+pub fn remove_global_name(name: &str) {
+    iup_open();
+    unsafe {
+        attributes::set_handle(name, ptr::null_mut());
+    }
+}
+
 fn iup_open() {
     check_thread();
     unsafe {
@@ -233,17 +360,89 @@ pub unsafe trait Control {
         unsafe { IupDetach(self.handle()); }
     }
 
-    fn reparent(&self, new_parent: &Container, ref_child: Option<&Control>) -> Result<(), ()> {
+    fn reparent(&self, new_parent: &Container, ref_child: Option<&Control>) -> Result<(), Error> {
         unsafe {
             let ref_child = ref_child.map(|c| c.handle()).unwrap_or(ptr::null_mut());
-            if IupReparent(self.handle(), new_parent.handle(), ref_child) == IUP_NOERROR {
-                Ok(())
+            Error::check("IupReparent", IupReparent(self.handle(), new_parent.handle(), ref_child))
+        }
+    }
+
+    /// Gets the value of any attribute. Panics if the value isn't valid for the attribute's type.
+    fn get<T: AttrValue>(&self, attr: Attr<T>) -> T where Self: Sized {
+        attr.get_handle(self.handle())
+    }
+
+    /// Gets the value of any attribute, returning an error if the value isn't valid for the
+    /// attribute's type.
+    fn try_get<T: AttrValue>(&self, attr: Attr<T>) -> Result<T, Error> where Self: Sized {
+        attr.try_get_handle(self.handle())
+    }
+
+    /// Sets the value of any attribute.
+    fn set<T: AttrValue>(&self, attr: Attr<T>, value: T) -> &Self where Self: Sized {
+        attr.set_handle(self.handle(), &value);
+        self
+    }
+
+    /// Removes an attribute's value so that it is inherited or uses its default again.
+    fn reset<T>(&self, attr: Attr<T>) -> &Self where Self: Sized {
+        attr.reset_handle(self.handle());
+        self
+    }
+
+    /// Returns the control's NAME, which identifies it within its dialog. See `Dialog::find`.
+    fn name(&self) -> String {
+        attributes::get_str_attribute(self.handle(), "NAME\0")
+    }
+
+    /// Sets the control's NAME, which identifies it within its dialog. See `Dialog::find`.
+    fn set_name(&self, name: &str) -> &Self where Self: Sized {
+        attributes::set_str_attribute(self.handle(), "NAME\0", name);
+        self
+    }
+
+    /// Returns a name that was given to this control using `set_global_name`. If it has more
+    /// than one, any of them may be returned.
+    fn global_name(&self) -> Option<String> {
+        unsafe {
+            let name = IupGetName(self.handle());
+            if name.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    /// Gives this control a name that is unique among all controls, so that it can be found from
+    /// anywhere using `find_global`. If another control already has the name, it loses it. The
+    /// name is removed when this control is destroyed.
+    fn set_global_name(&self, name: &str) -> &Self where Self: Sized {
+        unsafe {
+            attributes::set_handle(name, self.handle());
+        }
+        self
+    }
+
+    /// Returns the control this one is a child of, or `None` if it doesn't have a parent.
+    fn parent(&self) -> Option<AnyControl> {
+        unsafe {
+            let handle = IupGetParent(self.handle());
+            if handle.is_null() {
+                None
             } else {
-                Err(())
+                Some(AnyControl::from_handle(handle))
             }
         }
     }
 
+    /// Returns a reference to this control that doesn't keep it alive. A closure added to a
+    /// control's callback can capture a weak reference to it (or to its dialog) without making a
+    /// reference cycle.
+    fn downgrade(&self) -> WeakControl<Self> where Self: ControlType {
+        WeakControl::new(self)
+    }
+
     fn get_dialog(&self) -> Option<Dialog> {
         unsafe {
             let handle = IupGetDialog(self.handle());
@@ -331,18 +530,21 @@ impl ScreenPosition {
 }
 
 pub trait Popup : Control {
-    fn popup(&self, x: ScreenPosition, y: ScreenPosition) -> Result<(), ()> {
-        unsafe {
-            if IupPopup(self.handle(), x.to_int(), y.to_int()) == IUP_NOERROR {
-                Ok(())
-            } else {
-                Err(())
-            }
+    /// Shows the control modally, running a nested loop until it is hidden.
+    ///
+    /// A panic in a callback ends the nested loop and continues unwinding from this function, so
+    /// the code after it doesn't run. If `popup` is called from a callback, the panic then ends
+    /// the loop that called the callback, and so on until it is propagated out of `main_loop`.
+    fn popup(&self, x: ScreenPosition, y: ScreenPosition) -> Result<(), Error> {
+        let result = unsafe { IupPopup(self.handle(), x.to_int(), y.to_int()) };
+        if let Some(payload) = callbacks::take_panic_payload() {
+            callbacks::resume_panic(payload);
         }
+        Error::check("IupPopup", result)
     }
 }
 
-#[derive(Copy,Clone)]
+#[derive(Debug,Copy,Clone,PartialEq)]
 pub enum MouseButton {
     Button1,
     Button2,
@@ -363,7 +565,6 @@ impl MouseButton {
         }
     }
 
-    #[allow(dead_code)]
     fn to_int(self) -> c_int {
         match self {
             MouseButton::Button1 => IUP_BUTTON1,
@@ -375,20 +576,44 @@ impl MouseButton {
     }
 }
 
-#[derive(Clone)]
+/// The keyboard modifiers and mouse buttons held down during a mouse event. The default has none
+/// of them pressed.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub struct KeyboardMouseStatus {
-    shift_pressed: bool,
-    control_pressed: bool,
-    alt_pressed: bool,
-    sys_pressed: bool,
-    button1_pressed: bool,
-    button2_pressed: bool,
-    button3_pressed: bool,
-    button4_pressed: bool,
-    button5_pressed: bool,
+    pub shift_pressed: bool,
+    pub control_pressed: bool,
+    pub alt_pressed: bool,
+    pub sys_pressed: bool,
+    pub button1_pressed: bool,
+    pub button2_pressed: bool,
+    pub button3_pressed: bool,
+    pub button4_pressed: bool,
+    pub button5_pressed: bool,
 }
 
 impl KeyboardMouseStatus {
+    // Writes the status the way IUP passes it to callbacks, which the iup_is* macros read.
+    fn to_status_string(&self) -> [u8; 11] {
+        let mut s = *b"          \0";
+        let flags = [
+            (0, self.shift_pressed, b'S'),
+            (1, self.control_pressed, b'C'),
+            (2, self.button1_pressed, b'1'),
+            (3, self.button2_pressed, b'2'),
+            (4, self.button3_pressed, b'3'),
+            (6, self.alt_pressed, b'A'),
+            (7, self.sys_pressed, b'Y'),
+            (8, self.button4_pressed, b'4'),
+            (9, self.button5_pressed, b'5'),
+        ];
+        for &(i, pressed, c) in &flags {
+            if pressed {
+                s[i] = c;
+            }
+        }
+        s
+    }
+
     unsafe fn from_cstr(s: *const c_char) -> KeyboardMouseStatus {
         KeyboardMouseStatus {
             shift_pressed: iup_isshift(s),
@@ -413,12 +638,12 @@ pub enum VAlignment {
 }
 
 impl VAlignment {
-    fn from_str(s: &[u8]) -> Self {
+    fn from_str(s: &[u8]) -> Option<Self> {
         match s {
-            b"ATOP" => VAlignment::Top,
-            b"ACENTER" => VAlignment::Center,
-            b"ABOTTOM" => VAlignment::Bottom,
-            _ => panic!("unknown VAlignment"),
+            b"ATOP" => Some(VAlignment::Top),
+            b"ACENTER" => Some(VAlignment::Center),
+            b"ABOTTOM" => Some(VAlignment::Bottom),
+            _ => None,
         }
     }
 
@@ -440,12 +665,12 @@ pub enum HAlignment {
 }
 
 impl HAlignment {
-    fn from_str(s: &[u8]) -> Self {
+    fn from_str(s: &[u8]) -> Option<Self> {
         match s {
-            b"ALEFT" => HAlignment::Left,
-            b"ACENTER" => HAlignment::Center,
-            b"ARIGHT" => HAlignment::Right,
-            _ => panic!("unknown HAlignment"),
+            b"ALEFT" => Some(HAlignment::Left),
+            b"ACENTER" => Some(HAlignment::Center),
+            b"ARIGHT" => Some(HAlignment::Right),
+            _ => None,
         }
     }
 
@@ -466,11 +691,11 @@ pub enum Orientation {
 }
 
 impl Orientation {
-    fn from_str(s: &[u8]) -> Self {
+    fn from_str(s: &[u8]) -> Option<Self> {
         match s {
-            b"HORIZONTAL" => Orientation::Horizontal,
-            b"VERTICAL" => Orientation::Vertical,
-            _ => panic!("unknown Orientation"),
+            b"HORIZONTAL" => Some(Orientation::Horizontal),
+            b"VERTICAL" => Some(Orientation::Vertical),
+            _ => None,
         }
     }
 
@@ -6,6 +6,7 @@
  */
 
 use super::control_prelude::*;
+use super::Property;
 use attributes::set_attribute_ptr;
 use std::ffi::CStr;
 
@@ -21,38 +22,9 @@ impl List {
         }
     }
 
-    pub fn dropdown(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "DROPDOWN\0") == "YES"
-        }
-    }
-
-    pub fn set_dropdown(&self, dropdown: bool) -> &Self {
-        set_str_attribute(self.handle(), "DROPDOWN\0", if dropdown { "YES\0" } else { "NO\0" });
-        self
-    }
-
-    pub fn edit_box(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "EDITBOX\0") == "YES"
-        }
-    }
-
-    pub fn set_edit_box(&self, edit_box: bool) -> &Self {
-        set_str_attribute(self.handle(), "EDITBOX\0", if edit_box { "YES\0" } else { "NO\0" });
-        self
-    }
-
-    pub fn multiple(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "MULTIPLE\0") == "YES"
-        }
-    }
-
-    pub fn set_multiple(&self, multiple: bool) -> &Self {
-        set_str_attribute(self.handle(), "MULTIPLE\0", if multiple { "YES\0" } else { "NO\0" });
-        self
-    }
+    attr_methods!(pub dropdown, try_dropdown, set_dropdown: bool = attr::DROPDOWN);
+    attr_methods!(pub edit_box, try_edit_box, set_edit_box: bool = attr::EDITBOX);
+    attr_methods!(pub multiple, try_multiple, set_multiple: bool = attr::MULTIPLE);
 
     // An `index` of 0 is the first item.
     pub fn item(&self, index: usize) -> String {
@@ -100,12 +72,7 @@ impl List {
         self
     }
 
-    pub fn count(&self) -> usize {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "COUNT\0");
-            s.parse().unwrap()
-        }
-    }
+    attr_methods!(pub count, try_count: usize = attr::COUNT);
 
     /// Panics if `edit_box` is false.
     pub fn value_text(&self) -> String {
@@ -155,12 +122,32 @@ impl List {
         }
     }
 
+    /// Sets the selected item from `value` and keeps them the same, updating the property when
+    /// the user selects an item.
+    ///
+    /// Panics if `edit_box` is true or `multiple` is true.
+    pub fn bind_value_single(&self, value: &Property<Option<usize>>) -> &Self {
+        value.bind(self, |list, &value| {
+            if list.value_single() != value {
+                list.set_value_single(value);
+            }
+        });
+        let value = value.clone();
+        self.action_event().add(move |args: &ListActionArgs| {
+            // Changing the selection also calls the callback for the item that was deselected.
+            if args.selected {
+                value.set(Some(args.item_index));
+            }
+        });
+        self
+    }
+
     // visible_items
     // visible_columns
     // visible_lines
 }
 
-impl_control_traits!(List);
+impl_control_traits!(List, "list");
 
 impl ActiveAttribute for List {}
 impl ExpandAttribute for List {}
@@ -169,6 +156,10 @@ impl VisibleAttribute for List {}
 impl VisibleColumnsLinesAttribute for List {}
 
 impl MenuCommonCallbacks for List {}
+impl KAnyCallback for List {}
+impl DropFilesCallback for List {}
+impl DragSource for List {}
+impl DropTarget for List {}
 
 #[derive(Clone)]
 pub struct ListActionArgs<'a> {
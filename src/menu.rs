@@ -6,6 +6,7 @@
  */
 
 use super::control_prelude::*;
+use super::Key;
 use super::containers::{
     Container,
     wrapper_to_handle_vec,
@@ -43,7 +44,7 @@ impl Menu {
     }
 }
 
-impl_control_traits!(Menu);
+impl_control_traits!(Menu, "menu");
 
 impl Container for Menu {}
 
@@ -74,7 +75,7 @@ impl Submenu {
     }
 }
 
-impl_control_traits!(Submenu);
+impl_control_traits!(Submenu, "submenu");
 
 impl MenuSubitem for Submenu {}
 
@@ -106,13 +107,36 @@ impl Item {
     where &'a Self: CoerceUnsized<&'a Control> {
         Event::new(self as &'a Control, &ITEM_ACTION_CALLBACKS)
     }
+
+    /// Shows the text of a shortcut key at the right side of the item, or removes it if `key` is
+    /// `None`. This only changes the title; see `Dialog::add_item_shortcut` to make the key work.
+    pub fn set_shortcut_text(&self, key: Option<&Key>) -> &Self {
+        // IUP shows the part of the title after a tab as the item's shortcut.
+        let title = get_str_attribute(self.handle(), "TITLE\0");
+        let mut title = title.split('\t').next().unwrap_or("").to_owned();
+        if let Some(key) = key {
+            title.push('\t');
+            title.push_str(&key.to_string());
+        }
+        set_str_attribute(self.handle(), "TITLE\0", &title);
+        self
+    }
+
+    // Calls the action callbacks as if the item were clicked, if it is active.
+    pub(crate) fn activate(&self) {
+        if self.active() {
+            item_action_cb(self.handle());
+        }
+    }
 }
 
-impl_control_traits!(Item);
+impl_control_traits!(Item, "item");
 
 impl MenuSubitem for Item {}
 
 impl ActiveAttribute for Item {}
+impl ImageAttribute for Item {}
+impl PressedImageAttribute for Item {}
 impl TitleAttribute for Item {}
 
 
@@ -140,6 +164,6 @@ impl Separator {
     }
 }
 
-impl_control_traits!(Separator);
+impl_control_traits!(Separator, "separator");
 
 impl MenuSubitem for Separator {}
@@ -0,0 +1,124 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+use super::control_prelude::*;
+use super::handle_rc::add_ldestroy_callback;
+
+/// A value that notifies subscribers when it changes, and that controls can be bound to.
+///
+/// Cloning a `Property` creates another reference to the same value. Binding a control (with
+/// methods such as `Text::bind_value`) sets the control from the property, keeps it updated
+/// whenever the property is set, and sets the property when the user changes the control. A bound
+/// control keeps the property alive, but the property doesn't keep the control alive.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use clear_coat::*;
+/// let name = Property::new("Anonymous".to_owned());
+/// let text = Text::new();
+/// text.bind_value(&name);
+/// name.subscribe(|name| println!("name changed to {}", name));
+/// name.set("Sam".to_owned());
+/// assert_eq!(text.value(), "Sam");
+/// ```
+pub struct Property<T: 'static>(Rc<PropertyInner<T>>);
+
+struct PropertyInner<T: 'static> {
+    value: RefCell<T>,
+    // Copy-on-write is used the same way as in `CallbackRegistry`, so that subscribers can be
+    // added or removed while notifying.
+    subscribers: RefCell<Rc<Vec<(usize, Rc<RefCell<FnMut(&T)>>)>>>,
+    next_id: Cell<usize>,
+}
+
+/// Identifies a subscriber so that it can be removed with `Property::unsubscribe`.
+#[derive(Debug)]
+pub struct PropertyToken(usize);
+
+impl<T: Clone + PartialEq + 'static> Property<T> {
+    pub fn new(value: T) -> Property<T> {
+        Property(Rc::new(PropertyInner {
+            value: RefCell::new(value),
+            subscribers: RefCell::new(Rc::new(vec![])),
+            next_id: Cell::new(0),
+        }))
+    }
+
+    pub fn get(&self) -> T {
+        self.0.value.borrow().clone()
+    }
+
+    /// Changes the value and calls every subscriber with it. Does nothing if the value is equal to
+    /// the current one, so that a bound control updating the property doesn't loop.
+    pub fn set(&self, value: T) {
+        if *self.0.value.borrow() == value {
+            return;
+        }
+        *self.0.value.borrow_mut() = value.clone();
+        let subscribers = self.0.subscribers.borrow().clone();
+        for &(_, ref f) in subscribers.iter() {
+            (&mut *f.borrow_mut())(&value);
+        }
+    }
+
+    /// Adds a function that is called with the new value each time it changes.
+    pub fn subscribe<F: FnMut(&T) + 'static>(&self, f: F) -> PropertyToken {
+        let id = self.0.next_id.get();
+        self.0.next_id.set(id + 1);
+        let mut subscribers = self.0.subscribers.borrow_mut();
+        Rc::make_mut(&mut *subscribers).push((id, Rc::new(RefCell::new(f))));
+        PropertyToken(id)
+    }
+
+    /// Removes a function added with `subscribe`.
+    pub fn unsubscribe(&self, token: PropertyToken) {
+        let removed = {
+            let mut subscribers = self.0.subscribers.borrow_mut();
+            let subscribers = Rc::make_mut(&mut *subscribers);
+            let index = subscribers.iter().position(|&(id, _)| id == token.0)
+                                   .expect("failed to remove subscriber");
+            subscribers.remove(index)
+        };
+        // Dropped after the borrow ends in case dropping it drops a control.
+        drop(removed);
+    }
+
+    // Sets `control` from the property now and whenever the property changes, until the control
+    // is destroyed. The control's own callback should call `set` to update the property.
+    pub(crate) fn bind<C, F>(&self, control: &C, mut update: F)
+    where C: ControlType + 'static, F: FnMut(&C, &T) + 'static {
+        update(control, &self.get());
+        let weak_control = control.downgrade();
+        let token = self.subscribe(move |value| {
+            if let Some(control) = weak_control.upgrade() {
+                update(&control, value);
+            }
+        });
+        let weak_property = Rc::downgrade(&self.0);
+        add_ldestroy_callback(control.handle(), move |_| {
+            if let Some(inner) = weak_property.upgrade() {
+                Property(inner).unsubscribe(PropertyToken(token.0));
+            }
+        });
+    }
+}
+
+impl<T: 'static> Clone for Property<T> {
+    fn clone(&self) -> Property<T> {
+        Property(self.0.clone())
+    }
+}
+
+impl<T: fmt::Debug + 'static> fmt::Debug for Property<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Property").field(&*self.0.value.borrow()).finish()
+    }
+}
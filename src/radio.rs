@@ -32,7 +32,7 @@ impl Radio {
     }
 }
 
-impl_control_traits!(Radio);
+impl_control_traits!(Radio, "radio");
 
 impl Container for Radio {}
 impl NonDialogContainer for Radio {}
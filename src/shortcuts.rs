@@ -0,0 +1,78 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use iup_sys::*;
+use super::{CallbackAction, Control, Dialog, Error, Key};
+use super::common_attrs_cbs::KAnyCallback;
+use super::handle_rc::add_ldestroy_callback;
+
+type Shortcut = Rc<RefCell<FnMut()>>;
+
+// The shortcuts for each dialog. Like extra refs, they are removed when the dialog is destroyed.
+thread_local!(
+    static SHORTCUTS: RefCell<HashMap<*mut Ihandle, HashMap<Key, Shortcut>>> =
+        RefCell::new(HashMap::new())
+);
+
+// Returns an error if the dialog already has a shortcut for the key.
+pub fn add_shortcut(dialog: &Dialog, key: Key, f: Shortcut) -> Result<(), Error> {
+    let ih = dialog.handle();
+    let is_first = SHORTCUTS.with(|map| {
+        let mut map = map.borrow_mut();
+        let is_first = !map.contains_key(&ih);
+        let shortcuts = map.entry(ih).or_insert_with(|| {
+            add_ldestroy_callback(ih, |ih| {
+                // The shortcuts are dropped after the borrow ends in case dropping one drops a
+                // dialog.
+                let removed = SHORTCUTS.with(|map| map.borrow_mut().remove(&ih));
+                drop(removed);
+            });
+            HashMap::new()
+        });
+        if shortcuts.contains_key(&key) {
+            return Err(Error::DuplicateShortcut { key: key.to_string() });
+        }
+        shortcuts.insert(key, f);
+        Ok(is_first)
+    })?;
+    // One callback on the dialog handles all of its shortcuts. K_ANY is passed on to the dialog
+    // from whichever child has the focus unless the child handles the key itself.
+    if is_first {
+        dialog.k_any_event().add(move |key: &Key| {
+            let shortcut = SHORTCUTS.with(|map| {
+                map.borrow().get(&ih).and_then(|shortcuts| shortcuts.get(key).cloned())
+            });
+            match shortcut {
+                Some(f) => {
+                    (&mut *f.borrow_mut())();
+                    CallbackAction::Ignore
+                },
+                None => CallbackAction::Default,
+            }
+        });
+    }
+    Ok(())
+}
+
+// Returns true if the dialog had a shortcut for the key.
+pub fn remove_shortcut(dialog: &Dialog, key: &Key) -> bool {
+    let removed = SHORTCUTS.with(|map| {
+        map.borrow_mut().get_mut(&dialog.handle()).and_then(|shortcuts| shortcuts.remove(key))
+    });
+    removed.is_some()
+}
+
+pub fn shortcut_keys(dialog: &Dialog) -> Vec<Key> {
+    SHORTCUTS.with(|map| {
+        map.borrow().get(&dialog.handle()).map_or(vec![], |shortcuts| {
+            shortcuts.keys().cloned().collect()
+        })
+    })
+}
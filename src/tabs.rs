@@ -6,6 +6,9 @@
  */
 
 use super::control_prelude::*;
+use super::{Image, Property};
+use super::extra_refs::ExtraRefKey;
+use super::image::{get_image_attribute, set_image_attribute_with_owner};
 use super::containers::{
     Container,
     NonDialogContainer,
@@ -15,6 +18,7 @@ use super::containers::{
 pub struct TabInfo<'a, 'b> {
     title: &'a str,
     control: &'b Control,
+    image: Option<&'b Image>,
 }
 
 impl<'a, 'b> TabInfo<'a, 'b> {
@@ -22,6 +26,7 @@ impl<'a, 'b> TabInfo<'a, 'b> {
         TabInfo {
             title: "",
             control: control,
+            image: None,
         }
     }
 
@@ -29,6 +34,11 @@ impl<'a, 'b> TabInfo<'a, 'b> {
         self.title = title;
         self
     }
+
+    pub fn image(&mut self, image: &'b Image) -> &mut Self {
+        self.image = Some(image);
+        self
+    }
 }
 
 /// # Examples
@@ -57,6 +67,10 @@ impl<'a, 'b> TabInfo<'a, 'b> {
 #[derive(Clone)]
 pub struct Tabs(HandleRc);
 
+// The image of each tab is kept by the tab's control, so that it follows the control when tabs
+// are added or removed before it.
+const EXTRA_REF_TAB_IMAGE: ExtraRefKey = ExtraRefKey(7);
+
 impl Tabs {
     pub fn new() -> Self {
         unsafe {
@@ -77,18 +91,55 @@ impl Tabs {
         self
     }
 
+    /// A `pos` of 0 is the first tab.
+    pub fn tab_image(&self, pos: usize) -> Option<Image> {
+        get_image_attribute(self.handle(), &format!("TABIMAGE{}\0", pos))
+    }
+
+    /// Shows an image on a tab, or removes it if `image` is `None`. A `pos` of 0 is the first
+    /// tab. The tab's control keeps a reference to the image. Does nothing if there is no tab at
+    /// `pos`.
+    pub fn set_tab_image(&self, pos: usize, image: Option<&Image>) -> &Self {
+        let child = unsafe { IupGetChild(self.handle(), pos as c_int) };
+        if !child.is_null() {
+            set_image_attribute_with_owner(self.handle(), &format!("TABIMAGE{}\0", pos), child,
+                                           EXTRA_REF_TAB_IMAGE, image);
+        }
+        self
+    }
+
+    attr_methods!(
+        /// The position of the current tab. A position of 0 is the first tab.
+        pub value_pos, try_value_pos, set_value_pos: usize = attr::VALUEPOS);
+
+    /// Sets the current tab from `pos` and keeps them the same, updating the property when the
+    /// user changes tabs.
+    pub fn bind_value_pos(&self, pos: &Property<usize>) -> &Self {
+        pos.bind(self, |tabs, &pos| {
+            if tabs.value_pos() != pos {
+                tabs.set_value_pos(pos);
+            }
+        });
+        let pos = pos.clone();
+        self.tab_change_event().add(move |args: &TabChangeArgs| pos.set(args.new_pos));
+        self
+    }
+
     pub fn append_tabs(&self, info: &[&TabInfo]) -> &Self {
         let mut index = self.child_count();
         for ti in info {
             self.append(ti.control).expect("failed to append tab");
             self.set_tab_title(index, ti.title);
+            if ti.image.is_some() {
+                self.set_tab_image(index, ti.image);
+            }
             index += 1;
         }
         self
     }
 }
 
-impl_control_traits!(Tabs);
+impl_control_traits!(Tabs, "tabs");
 
 impl Container for Tabs {}
 impl NonDialogContainer for Tabs {}
@@ -101,5 +152,34 @@ impl VisibleAttribute for Tabs {}
 
 impl MenuCommonCallbacks for Tabs {}
 impl GetKillFocusCallbacks for Tabs {}
+impl KAnyCallback for Tabs {}
 impl EnterLeaveWindowCallbacks for Tabs {}
 
+#[derive(Clone)]
+pub struct TabChangeArgs {
+    pub new_pos: usize,
+    pub old_pos: usize,
+    _dummy: (),
+}
+
+impl_callbacks! {
+    Tabs {
+        "TABCHANGEPOS_CB\0" => tab_change_event {
+            TAB_CHANGE_CALLBACKS<FnMut(&TabChangeArgs), TabChangeCallbackToken>
+        }
+        unsafe extern fn tab_change_pos_cb(ih: *mut Ihandle, new_pos: c_int, old_pos: c_int) -> c_int {
+            with_callbacks(ih, &TAB_CHANGE_CALLBACKS, |cbs| {
+                let args = TabChangeArgs {
+                    new_pos: new_pos as usize,
+                    old_pos: old_pos as usize,
+                    _dummy: (),
+                };
+                for cb in cbs {
+                    (&mut *cb.1.borrow_mut())(&args);
+                }
+                IUP_DEFAULT
+            })
+        }
+    }
+}
+
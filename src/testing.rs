@@ -0,0 +1,272 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+//! Simulating user input for automated tests.
+//!
+//! Each function changes the control the way the user would (such as setting the value of a
+//! `Toggle` before its callback is called), then calls the callback IUP has registered on the
+//! control, the same as for a real event. The controls still have to be created, so tests need a
+//! display (such as a virtual X server), but the dialogs don't have to be shown or a main loop
+//! run.
+//!
+//! A panic in a callback is propagated out of the function that simulated the event. If the
+//! callback returns `CallbackAction::Close`, the innermost main loop is exited, if one is running.
+//!
+//! ```no_run
+//! # use clear_coat::*;
+//! # use clear_coat::testing;
+//! let button = Button::new();
+//! button.action_event().add(|| println!("clicked"));
+//! testing::click(&button);
+//! ```
+
+use std::ffi::CString;
+use std::mem;
+use std::path::Path;
+use libc::c_void;
+use super::control_prelude::*;
+use super::callbacks;
+use super::common_attrs_cbs::{ButtonCallback, CanvasCallbacks, DropFilesCallback, MotionArgs};
+use super::{Button, ButtonArgs, DragSource, DropTarget, Key, List, Tabs, Text, Toggle};
+
+// iup-sys declares the return value as non-nullable, but it is null when no callback is set.
+extern {
+    #[link_name = "IupGetCallback"]
+    fn get_callback(ih: *mut Ihandle, name: *const c_char) -> Option<Icallback>;
+}
+
+fn find_callback(ih: *mut Ihandle, name: &str) -> Option<Icallback> {
+    ::check_thread();
+    let mut name_buf = SmallVec::<[u8; 64]>::new();
+    unsafe { get_callback(ih, str_to_c_vec(name, &mut name_buf)) }
+}
+
+// Calls `call` with the callback named `name` if one is set, then handles the result the way the
+// main loop would. Returns `IUP_DEFAULT` if no callback is set.
+fn run_callback<F>(ih: *mut Ihandle, name: &str, call: F) -> c_int
+where F: FnOnce(Icallback) -> c_int {
+    let result = find_callback(ih, name).map_or(IUP_DEFAULT, call);
+    if let Some(payload) = callbacks::take_panic_payload() {
+        callbacks::resume_panic(payload);
+    }
+    if result == IUP_CLOSE && ::main_loop_level() > 0 {
+        ::exit_loop();
+    }
+    result
+}
+
+fn to_action(result: c_int) -> CallbackAction {
+    match result {
+        IUP_CLOSE => CallbackAction::Close,
+        IUP_IGNORE => CallbackAction::Ignore,
+        IUP_CONTINUE => CallbackAction::Continue,
+        _ => CallbackAction::Default,
+    }
+}
+
+/// Clicks a button, calling its `action_event` callbacks.
+pub fn click(button: &Button) -> CallbackAction {
+    to_action(run_callback(button.handle(), "ACTION", |cb| cb(button.handle())))
+}
+
+/// Clicks a toggle, turning it on if it was off and off otherwise, and calls its `action_event`
+/// callbacks.
+pub fn toggle(toggle: &Toggle) {
+    set_toggle(toggle, !toggle.is_on());
+}
+
+/// Turns a toggle on or off as if the user clicked it. The `action_event` callbacks are not
+/// called if it is already in that state, the same as clicking a radio button that is already
+/// selected.
+pub fn set_toggle(toggle: &Toggle, on: bool) {
+    if toggle.is_on() == on {
+        return;
+    }
+    toggle.set_on(on);
+    run_callback(toggle.handle(), "ACTION", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, c_int) -> c_int = mem::transmute(cb);
+        cb(toggle.handle(), if on { 1 } else { 0 })
+    });
+}
+
+/// Selects an item in a list as if the user clicked it. The `action_event` callbacks are called
+/// for the item that was deselected, if any, and then for the new item, and then the
+/// `value_changed_event` callbacks are called. Nothing is called if the item is already selected.
+///
+/// Panics if `edit_box` or `multiple` is true, or if `index` is out of range.
+pub fn choose_item(list: &List, index: usize) {
+    let old = list.value_single();
+    if old == Some(index) {
+        return;
+    }
+    list.set_value_single(Some(index));
+    let ih = list.handle();
+    let list_action = |item: usize, state: c_int| {
+        let text = CString::new(list.item(item)).unwrap_or_default();
+        run_callback(ih, "ACTION", |cb| unsafe {
+            let cb: extern fn(*mut Ihandle, *mut c_char, c_int, c_int) -> c_int =
+                mem::transmute(cb);
+            cb(ih, text.as_ptr() as *mut c_char, item as c_int + 1, state)
+        });
+    };
+    if let Some(old) = old {
+        list_action(old, 0);
+    }
+    list_action(index, 1);
+    run_callback(ih, "VALUECHANGED_CB", |cb| cb(ih));
+}
+
+/// Switches to a tab as if the user clicked it, calling the `tab_change_event` callbacks. Nothing
+/// is called if the tab is already the current one.
+pub fn change_tab(tabs: &Tabs, pos: usize) {
+    let old = tabs.value_pos();
+    if old == pos {
+        return;
+    }
+    tabs.set_value_pos(pos);
+    let ih = tabs.handle();
+    run_callback(ih, "TABCHANGEPOS_CB", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, c_int, c_int) -> c_int = mem::transmute(cb);
+        cb(ih, pos as c_int, old as c_int)
+    });
+}
+
+/// Types text at the end of a `Text`, one character at a time. After each character is added,
+/// the `value_changed_event` callbacks are called.
+pub fn type_text(text: &Text, typed: &str) {
+    let ih = text.handle();
+    let mut value = text.value();
+    for c in typed.chars() {
+        value.push(c);
+        text.set_value(&value);
+        run_callback(ih, "VALUECHANGED_CB", |cb| cb(ih));
+    }
+}
+
+/// Presses a key while `control` has the focus, calling its `k_any_event` callbacks. The same as
+/// for a real key press, if the control has no callbacks or they return
+/// `CallbackAction::Continue`, the key is passed on to the parent's callbacks, up to the dialog.
+///
+/// This doesn't type the key into a `Text`; use `type_text` for that.
+pub fn press_key(control: &Control, key: &Key) -> CallbackAction {
+    let code = key.to_iup_code() as c_int;
+    let mut ih = control.handle();
+    while !ih.is_null() {
+        if find_callback(ih, "K_ANY").is_some() {
+            let result = run_callback(ih, "K_ANY", |cb| unsafe {
+                let cb: extern fn(*mut Ihandle, c_int) -> c_int = mem::transmute(cb);
+                cb(ih, code)
+            });
+            if result != IUP_CONTINUE {
+                return to_action(result);
+            }
+        }
+        ih = unsafe { IupGetParent(ih) };
+    }
+    CallbackAction::Default
+}
+
+/// Sends a mouse button press or release to a control, calling its `button_event` callbacks.
+pub fn send_button<C: ButtonCallback>(control: &C, args: &ButtonArgs) -> CallbackAction {
+    let ih = control.handle();
+    let mut status = args.status.to_status_string();
+    to_action(run_callback(ih, "BUTTON_CB", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, c_int, c_int, c_int, c_int, *mut c_char) -> c_int =
+            mem::transmute(cb);
+        cb(ih,
+           args.button.to_int(),
+           if args.pressed { 1 } else { 0 },
+           args.x as c_int,
+           args.y as c_int,
+           status.as_mut_ptr() as *mut c_char)
+    }))
+}
+
+/// Drops files on a control, calling its `drop_files_event` callbacks once for each file. Returns
+/// early if a callback returns `CallbackAction::Ignore`.
+pub fn drop_files<C, P>(control: &C, paths: &[P], x: i32, y: i32)
+where C: DropFilesCallback, P: AsRef<Path> {
+    let ih = control.handle();
+    for (i, path) in paths.iter().enumerate() {
+        let path = CString::new(path.as_ref().to_string_lossy().into_owned()).unwrap_or_default();
+        let remaining = (paths.len() - i - 1) as c_int;
+        let result = run_callback(ih, "DROPFILES_CB", |cb| unsafe {
+            let cb: extern fn(*mut Ihandle, *const c_char, c_int, c_int, c_int) -> c_int =
+                mem::transmute(cb);
+            cb(ih, path.as_ptr(), remaining, x as c_int, y as c_int)
+        });
+        if result == IUP_IGNORE {
+            break;
+        }
+    }
+}
+
+/// Drags from `source`, starting at (`source_x`, `source_y`), and drops on `target` at
+/// (`target_x`, `target_y`), calling the functions given to `DragSource::set_drag_source` and
+/// `DropTarget::add_drop_target`. Returns false if the drag wasn't started or if the target
+/// doesn't accept the source's type of data.
+pub fn drag_and_drop<S, T>(source: &S, source_x: i32, source_y: i32,
+                           target: &T, target_x: i32, target_y: i32) -> bool
+where S: DragSource, T: DropTarget {
+    let source_ih = source.handle();
+    let target_ih = target.handle();
+    let drag_type = get_str_attribute(source_ih, "DRAGTYPES\0");
+    let drop_types = get_str_attribute(target_ih, "DROPTYPES\0");
+    if !drop_types.split(',').any(|t| t == drag_type) {
+        return false;
+    }
+    let result = run_callback(source_ih, "DRAGBEGIN_CB", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, c_int, c_int) -> c_int = mem::transmute(cb);
+        cb(source_ih, source_x as c_int, source_y as c_int)
+    });
+    if result == IUP_IGNORE {
+        return false;
+    }
+    let c_type = CString::new(&drag_type[..]).unwrap_or_default();
+    let size = run_callback(source_ih, "DRAGDATASIZE_CB", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, *const c_char) -> c_int = mem::transmute(cb);
+        cb(source_ih, c_type.as_ptr())
+    });
+    let mut data = vec![0u8; size.max(0) as usize];
+    run_callback(source_ih, "DRAGDATA_CB", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, *const c_char, *mut c_void, c_int) -> c_int =
+            mem::transmute(cb);
+        cb(source_ih, c_type.as_ptr(), data.as_mut_ptr() as *mut c_void, size)
+    });
+    drop_data(target, &drag_type, &data, target_x, target_y);
+    // An action of 1 means the data was copied.
+    run_callback(source_ih, "DRAGEND_CB", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, c_int) -> c_int = mem::transmute(cb);
+        cb(source_ih, 1)
+    });
+    true
+}
+
+/// Drops data of type `type_name` on `target` at (`x`, `y`), calling the functions given to
+/// `DropTarget::add_drop_target` for that type. Unlike `drag_and_drop`, the data is dropped even
+/// if the target doesn't accept the type.
+pub fn drop_data<T: DropTarget>(target: &T, type_name: &str, data: &[u8], x: i32, y: i32) {
+    let ih = target.handle();
+    let c_type = CString::new(type_name).unwrap_or_default();
+    let mut data = data.to_owned();
+    run_callback(ih, "DROPDATA_CB", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, *const c_char, *mut c_void, c_int, c_int, c_int) -> c_int =
+            mem::transmute(cb);
+        cb(ih, c_type.as_ptr(), data.as_mut_ptr() as *mut c_void, data.len() as c_int,
+           x as c_int, y as c_int)
+    });
+}
+
+/// Sends mouse movement to a control, calling its `motion_event` callbacks.
+pub fn send_motion<C: CanvasCallbacks>(control: &C, args: &MotionArgs) {
+    let ih = control.handle();
+    let mut status = args.status.to_status_string();
+    run_callback(ih, "MOTION_CB", |cb| unsafe {
+        let cb: extern fn(*mut Ihandle, c_int, c_int, *mut c_char) -> c_int = mem::transmute(cb);
+        cb(ih, args.x as c_int, args.y as c_int, status.as_mut_ptr() as *mut c_char)
+    });
+}
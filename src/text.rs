@@ -6,6 +6,7 @@
  */
 
 use super::control_prelude::*;
+use super::Property;
 use std::borrow::Cow;
 
 #[derive(Clone)]
@@ -41,35 +42,35 @@ impl Text {
         self
     }
 
-    /// If true, this control is multiline, and the current text is not empty, then a `'\n'`
-    /// character will be automatically inserted before text appended using `append`.
-    ///
-    /// The default is true.
-    pub fn append_newline(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "APPENDNEWLINE\0") == "YES"
-        }
-    }
+    attr_methods!(
+        /// If true, this control is multiline, and the current text is not empty, then a `'\n'`
+        /// character will be automatically inserted before text appended using `append`.
+        ///
+        /// The default is true.
+        pub append_newline, try_append_newline, set_append_newline: bool = attr::APPENDNEWLINE);
 
-    /// See [`append_newline`](./struct.Text.html#method.append_newline)
-    pub fn set_append_newline(&self, enabled: bool) -> &Self {
-        set_str_attribute(self.handle(), "APPENDNEWLINE\0", if enabled { "YES\0" } else { "NO\0" });
-        self
-    }
-
-    pub fn multiline(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "MULTILINE\0") == "YES"
-        }
-    }
+    attr_methods!(pub multiline, try_multiline, set_multiline: bool = attr::MULTILINE);
 
-    pub fn set_multiline(&self, multiline: bool) -> &Self {
-        set_str_attribute(self.handle(), "MULTILINE\0", if multiline { "YES\0" } else { "NO\0" });
+    /// Sets the text from `value` and keeps them the same, updating the property when the user
+    /// edits the text.
+    pub fn bind_value(&self, value: &Property<String>) -> &Self {
+        value.bind(self, |text, value| {
+            if text.value() != *value {
+                text.set_value(value);
+            }
+        });
+        let weak_self = self.downgrade();
+        let value = value.clone();
+        self.value_changed_event().add(move || {
+            if let Some(text) = weak_self.upgrade() {
+                value.set(text.value());
+            }
+        });
         self
     }
 }
 
-impl_control_traits!(Text);
+impl_control_traits!(Text, "text", "multiline");
 
 impl ActiveAttribute for Text {}
 impl CanFocusAttribute for Text {}
@@ -82,6 +83,10 @@ impl VisibleColumnsLinesAttribute for Text {}
 
 impl MenuCommonCallbacks for Text {}
 impl GetKillFocusCallbacks for Text {}
+impl KAnyCallback for Text {}
+impl DropFilesCallback for Text {}
+impl DragSource for Text {}
+impl DropTarget for Text {}
 impl EnterLeaveWindowCallbacks for Text {}
 impl ValueChangedCallback for Text {}
 
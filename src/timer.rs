@@ -22,17 +22,7 @@ impl Timer {
         }
     }
 
-    pub fn time(&self) -> u32 {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "TIME\0");
-            s.parse().expect("could not convert TIME to an integer")
-        }
-    }
-
-    pub fn set_time(&self, time: u32) -> &Self {
-        set_str_attribute(self.handle(), "TIME\0", &format!("{}\0", time));
-        self
-    }
+    attr_methods!(pub time, try_time, set_time: u32 = attr::TIME);
 
     pub fn is_running(&self) -> bool {
         unsafe {
@@ -56,7 +46,7 @@ impl Timer {
     }
 }
 
-impl_control_traits!(Timer);
+impl_control_traits!(Timer, "timer");
 
 impl_callbacks! {
     Timer {
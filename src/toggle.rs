@@ -5,7 +5,9 @@
  * modified, or distributed except according to those terms.
  */
 
+use std::borrow::Cow;
 use super::control_prelude::*;
+use super::Property;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ToggleState {
@@ -15,12 +17,12 @@ pub enum ToggleState {
 }
 
 impl ToggleState {
-    fn from_str(s: &[u8]) -> Self {
+    fn from_str(s: &[u8]) -> Option<Self> {
         match s {
-            b"ON" => ToggleState::On,
-            b"OFF" => ToggleState::Off,
-            b"NOTDEF" => ToggleState::NotDef,
-            _ => panic!("unknown ToggleState"),
+            b"ON" => Some(ToggleState::On),
+            b"OFF" => Some(ToggleState::Off),
+            b"NOTDEF" => Some(ToggleState::NotDef),
+            _ => None,
         }
     }
 
@@ -33,6 +35,13 @@ impl ToggleState {
     }
 }
 
+impl AttrValue for ToggleState {
+    fn parse_attr(s: &str) -> Option<Self> { ToggleState::from_str(s.as_bytes()) }
+    fn format_attr(&self) -> Cow<'static, str> { self.to_str().into() }
+}
+
+const VALUE: Attr<ToggleState> = Attr::new("VALUE\0");
+
 #[derive(Clone)]
 pub struct Toggle(HandleRc);
 
@@ -45,17 +54,7 @@ impl Toggle {
         }
     }
 
-    pub fn value(&self) -> ToggleState {
-        unsafe {
-            let s = get_str_attribute_slice(self.handle(), "VALUE\0");
-            ToggleState::from_str(s.as_bytes())
-        }
-    }
-
-    pub fn set_value(&self, value: ToggleState) -> &Self {
-        set_str_attribute(self.handle(), "VALUE\0", value.to_str());
-        self
-    }
+    attr_methods!(pub value, try_value, set_value: ToggleState = VALUE);
 
     pub fn is_on(&self) -> bool {
         self.value() == ToggleState::On
@@ -66,30 +65,38 @@ impl Toggle {
         self
     }
 
-    pub fn three_state(&self) -> bool {
-        unsafe {
-            get_str_attribute_slice(self.handle(), "3STATE\0") == "YES"
-        }
-    }
-
-    pub fn set_three_state(&self, enabled: bool) -> &Self {
-        set_str_attribute(self.handle(), "3STATE\0", if enabled { "YES\0" } else { "NO\0" });
+    attr_methods!(pub three_state, try_three_state, set_three_state: bool = attr::THREESTATE);
+
+    /// Sets whether the toggle is on from `on` and keeps them the same, updating the property
+    /// when the user clicks the toggle.
+    pub fn bind_on(&self, on: &Property<bool>) -> &Self {
+        on.bind(self, |toggle, &on| {
+            if toggle.is_on() != on {
+                toggle.set_on(on);
+            }
+        });
+        let on = on.clone();
+        self.action_event().add(move |checked| on.set(checked));
         self
     }
 }
 
-impl_control_traits!(Toggle);
+impl_control_traits!(Toggle, "toggle");
 
 impl ActiveAttribute for Toggle {}
 impl CanFocusAttribute for Toggle {}
 impl ExpandAttribute for Toggle {}
+impl ImageAttribute for Toggle {}
+impl InactiveImageAttribute for Toggle {}
 impl MinMaxSizeAttribute for Toggle {}
+impl PressedImageAttribute for Toggle {}
 impl TipAttribute for Toggle {}
 impl TitleAttribute for Toggle {}
 impl VisibleAttribute for Toggle {}
 
 impl MenuCommonCallbacks for Toggle {}
 impl EnterLeaveWindowCallbacks for Toggle {}
+impl KAnyCallback for Toggle {}
 
 impl_callbacks! {
     Toggle {
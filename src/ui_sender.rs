@@ -0,0 +1,191 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::{Arc, Mutex};
+use libc::{c_char, c_double, c_int, c_void};
+use iup_sys::*;
+use super::callbacks::{handle_callback_panic, is_panic_pending};
+
+// iup-sys was generated from IUP 3.12, which predates `IupPostMessage`.
+extern {
+    fn IupPostMessage(ih: *mut Ihandle, s: *const c_char, i: c_int, d: c_double, p: *mut c_void);
+}
+
+struct Queue {
+    msgs: VecDeque<Box<FnOnce() + Send>>,
+    closed: bool,
+}
+
+struct Channel {
+    queue: Mutex<Queue>,
+    // The element that receives POSTMESSAGE_CB. It is never destroyed, and it is only passed to
+    // `IupPostMessage`, which IUP documents as safe to call from any thread.
+    receiver: *mut Ihandle,
+}
+
+unsafe impl Send for Channel {}
+unsafe impl Sync for Channel {}
+
+thread_local!(static RECEIVER: Cell<*mut Ihandle> = Cell::new(ptr::null_mut()));
+
+// The channel that `UiSender::new` hands out until the outermost `main_loop` returns.
+thread_local!(static CHANNEL: RefCell<Option<Arc<Channel>>> = RefCell::new(None));
+
+fn get_receiver() -> *mut Ihandle {
+    RECEIVER.with(|cell| {
+        if cell.get().is_null() {
+            unsafe {
+                let ih = IupUser();
+                IupSetCallback(ih, "POSTMESSAGE_CB\0".as_ptr() as *const c_char,
+                               mem::transmute::<_, Icallback>(post_message_cb as usize));
+                cell.set(ih);
+            }
+        }
+        cell.get()
+    })
+}
+
+extern fn post_message_cb(_ih: *mut Ihandle, _s: *mut c_char, _i: c_int, _d: c_double,
+                          _p: *mut c_void) -> c_int {
+    let channel = CHANNEL.with(|cell| cell.borrow().clone());
+    let channel = match channel {
+        Some(channel) => channel,
+        None => return IUP_DEFAULT,
+    };
+    // Take everything queued so far, and don't hold the lock while running closures so that
+    // they can post more closures.
+    let msgs = mem::replace(&mut channel.queue.lock().unwrap().msgs, VecDeque::new());
+    let mut msgs = msgs.into_iter();
+    // Each closure is run on its own so that a panic the hook handles doesn't lose the rest.
+    while let Some(f) = msgs.next() {
+        if let Err(err) = panic::catch_unwind(AssertUnwindSafe(f)) {
+            handle_callback_panic(err);
+            if is_panic_pending() {
+                // The loop is exiting, so the rest go back on the queue for the next loop.
+                let mut queue = channel.queue.lock().unwrap();
+                let was_empty = queue.msgs.is_empty();
+                for f in msgs.rev() {
+                    queue.msgs.push_front(f);
+                }
+                if was_empty && !queue.msgs.is_empty() {
+                    unsafe {
+                        IupPostMessage(channel.receiver, ptr::null(), 0, 0.0, ptr::null_mut());
+                    }
+                }
+                break;
+            }
+        }
+    }
+    IUP_DEFAULT
+}
+
+// Called when the outermost `main_loop` returns. Closures still in the queue are dropped, and
+// posting to any existing `UiSender` fails from now on.
+pub fn close_channel() {
+    let channel = CHANNEL.with(|cell| cell.borrow_mut().take());
+    if let Some(channel) = channel {
+        let msgs = {
+            let mut queue = channel.queue.lock().unwrap();
+            queue.closed = true;
+            mem::replace(&mut queue.msgs, VecDeque::new())
+        };
+        drop(msgs);
+    }
+}
+
+/// The error returned by `UiSender::post` when the main loop has exited. It contains the closure
+/// that couldn't be posted.
+pub struct PostError<F>(pub F);
+
+impl<F> fmt::Debug for PostError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("PostError(..)")
+    }
+}
+
+impl<F> fmt::Display for PostError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("posting to a UI thread whose main loop has exited")
+    }
+}
+
+impl<F> Error for PostError<F> {
+    fn description(&self) -> &str {
+        "posting to a UI thread whose main loop has exited"
+    }
+}
+
+/// A handle for running code on the UI thread from any other thread.
+///
+/// Closures are run by `main_loop` (or a `Popup::popup` loop) in the order they were posted, and
+/// the loop is woken up as soon as one is posted. A closure that panics ends the loop, and the
+/// panic is propagated out of `main_loop` the same as for a panic in a callback, unless the
+/// callback panic hook handles it. Either way, the closures posted after it stay queued: they are
+/// run by the next loop that runs, or dropped if the panic ends the outermost `main_loop`.
+///
+/// Once the outermost `main_loop` returns, every existing `UiSender` is closed, and `post` returns
+/// an error. A `UiSender` created after that can be used with the next call to `main_loop`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use clear_coat::*;
+/// use std::thread;
+///
+/// let sender = UiSender::new();
+/// thread::spawn(move || {
+///     let answer = 6 * 7;
+///     sender.post(move || println!("the answer is {}", answer)).ok();
+/// });
+/// ```
+#[derive(Clone)]
+pub struct UiSender(Arc<Channel>);
+
+impl UiSender {
+    /// Creates a sender for the current thread's main loop. This must be called on the UI thread.
+    pub fn new() -> UiSender {
+        ::iup_open();
+        let receiver = get_receiver();
+        CHANNEL.with(|cell| {
+            let mut channel = cell.borrow_mut();
+            if channel.is_none() {
+                *channel = Some(Arc::new(Channel {
+                    queue: Mutex::new(Queue { msgs: VecDeque::new(), closed: false }),
+                    receiver: receiver,
+                }));
+            }
+            UiSender(channel.as_ref().unwrap().clone())
+        })
+    }
+
+    /// Queues `f` to be run on the UI thread. If the main loop has exited, `f` is returned in the
+    /// error.
+    pub fn post<F>(&self, f: F) -> Result<(), PostError<F>>
+    where F: FnOnce() + Send + 'static {
+        let mut queue = self.0.queue.lock().unwrap();
+        if queue.closed {
+            return Err(PostError(f));
+        }
+        let was_empty = queue.msgs.is_empty();
+        queue.msgs.push_back(Box::new(f));
+        // If the queue wasn't empty, a wake-up is already pending and will run this closure too.
+        // The lock is still held here so that `close_channel` can't run in between.
+        if was_empty {
+            unsafe {
+                IupPostMessage(self.0.receiver, ptr::null(), 0, 0.0, ptr::null_mut());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use iup_sys::*;
+use super::ControlType;
+use super::handle_rc::get_alive_flag;
+
+/// A reference to a control that doesn't keep it alive. Created with `Control::downgrade`.
+///
+/// `upgrade` returns a wrapper for the control as long as it exists, even if there are no other
+/// wrappers for it (such as a control that has been added to a container). Once the control has
+/// been destroyed, either because its last wrapper was dropped or by `IupDestroy`, `upgrade`
+/// returns `None`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use clear_coat::*;
+/// # use clear_coat::common_attrs_cbs::*;
+/// let dialog = Dialog::new();
+/// let button = Button::new();
+/// dialog.append(&button).unwrap();
+/// let weak_dialog = dialog.downgrade();
+/// button.action_event().add(move || {
+///     if let Some(dialog) = weak_dialog.upgrade() {
+///         dialog.hide().unwrap();
+///     }
+/// });
+/// ```
+pub struct WeakControl<T: ControlType> {
+    ih: *mut Ihandle,
+    alive: Rc<Cell<bool>>,
+    phantom: PhantomData<T>,
+}
+
+impl<T: ControlType> WeakControl<T> {
+    pub(crate) fn new(control: &T) -> WeakControl<T> {
+        let ih = control.handle();
+        WeakControl { ih: ih, alive: get_alive_flag(ih), phantom: PhantomData }
+    }
+
+    /// Returns a wrapper for the control, or `None` if it has been destroyed.
+    pub fn upgrade(&self) -> Option<T> {
+        if self.alive.get() {
+            Some(unsafe { T::from_handle_unchecked(self.ih) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: ControlType> Clone for WeakControl<T> {
+    fn clone(&self) -> WeakControl<T> {
+        WeakControl { ih: self.ih, alive: self.alive.clone(), phantom: PhantomData }
+    }
+}
+
+impl<T: ControlType> fmt::Debug for WeakControl<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.alive.get() {
+            write!(f, "WeakControl({:?})", self.ih)
+        } else {
+            f.write_str("WeakControl(destroyed)")
+        }
+    }
+}
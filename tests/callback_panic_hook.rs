@@ -0,0 +1,41 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::panic;
+use std::rc::Rc;
+use clear_coat::*;
+
+// Tests that the panic hook sees each callback panic once, that returning true keeps the loop
+// running, and that returning false propagates the panic.
+
+#[test]
+fn test_callback_panic_hook() {
+    let seen = Rc::new(RefCell::new(vec![]));
+    let seen2 = seen.clone();
+    set_callback_panic_hook(move |payload| {
+        let message = payload.downcast_ref::<&'static str>().cloned().unwrap_or("other");
+        seen2.borrow_mut().push(message);
+        message == "handled"
+    });
+
+    let sender = UiSender::new();
+    sender.post(|| panic!("handled")).ok().unwrap();
+    sender.post(|| {
+        let dialog = Dialog::new();
+        UiSender::new().post(|| panic!("not handled")).ok().unwrap();
+        let _ = dialog.popup(ScreenPosition::Center, ScreenPosition::Center);
+    }).ok().unwrap();
+
+    let result = panic::catch_unwind(|| main_loop());
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&'static str>(), Some(&"not handled"));
+    assert_eq!(*seen.borrow(), vec!["handled", "not handled"]);
+    clear_callback_panic_hook();
+}
@@ -0,0 +1,39 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#[macro_use]
+extern crate clear_coat;
+
+use clear_coat::*;
+
+// Tests that children and parents are returned wrapped in the type for their class, and that a
+// child without a wrapper of its own is kept alive by the wrapper the iterator returns.
+
+#[test]
+fn test_children() {
+    let text = Text::new();
+    let dialog = Dialog::with_child(&vbox!(
+        &Button::new(),
+        &text,
+        &Label::new(),
+    ));
+    let vbox = dialog.children().next().unwrap().downcast::<Vbox>().ok().unwrap();
+
+    let class_names: Vec<_> = vbox.children().map(|c| c.class_name()).collect();
+    assert_eq!(class_names, ["button", "text", "label"]);
+
+    let mut children = vbox.children();
+    assert!(match children.next() { Some(AnyControl::Button(_)) => true, _ => false });
+    let text2 = children.next().unwrap().downcast::<Text>().ok().unwrap();
+    text2.set_value("hello");
+    assert_eq!(text.value(), "hello");
+    assert!(children.next().unwrap().downcast::<Button>().is_err());
+    assert!(children.next().is_none());
+
+    assert!(match text.parent() { Some(AnyControl::Vbox(_)) => true, _ => false });
+    assert!(dialog.parent().is_none());
+}
@@ -0,0 +1,39 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+
+// Tests that text, images, and custom formats can be copied to and read from the clipboard, and
+// that copying one replaces the others.
+
+#[test]
+fn test_clipboard() {
+    let clipboard = Clipboard::new();
+    clipboard.add_format("application/x-clear-coat-test");
+
+    clipboard.set_text("Clear Coat");
+    assert!(clipboard.is_text_available());
+    assert_eq!(clipboard.text(), Some("Clear Coat".to_owned()));
+
+    clipboard.set_format_data("application/x-clear-coat-test", &[1, 2, 0, 3]);
+    assert!(clipboard.is_format_available("application/x-clear-coat-test"));
+    assert!(!clipboard.is_text_available());
+    assert_eq!(clipboard.format_data("application/x-clear-coat-test"), Some(vec![1, 2, 0, 3]));
+
+    let image = Image::from_rgba(2, 1, &[255, 0, 0, 255, 0, 0, 255, 255]);
+    clipboard.set_image(&image);
+    assert!(clipboard.is_image_available());
+    assert!(!clipboard.is_format_available("application/x-clear-coat-test"));
+    let pasted = clipboard.image().unwrap();
+    assert_eq!((pasted.width(), pasted.height()), (2, 1));
+
+    clipboard.clear();
+    assert!(!clipboard.is_image_available());
+    assert_eq!(clipboard.text(), None);
+}
@@ -0,0 +1,54 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use clear_coat::testing;
+
+// Tests that shortcuts fire from a focused child, that duplicates are reported, that item
+// shortcuts show their text, and that shortcuts are dropped with the dialog.
+
+#[test]
+fn test_dialog_shortcuts() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let text = Text::new();
+    let dialog = Dialog::with_child(&text);
+    let log2 = log.clone();
+    dialog.add_shortcut("Ctrl+S", move || log2.borrow_mut().push("save")).unwrap();
+    match dialog.add_shortcut("control+s", || {}) {
+        Err(Error::DuplicateShortcut { ref key }) if key == "Ctrl+S" => {},
+        _ => panic!("duplicate shortcut not reported"),
+    }
+    assert!(dialog.add_shortcut("Ctrl+Nothing", || {}).is_err());
+
+    let open = Item::with_title("Open");
+    let log2 = log.clone();
+    open.action_event().add(move || log2.borrow_mut().push("open"));
+    dialog.set_menu(Some(&Menu::with_children(&[&open])));
+    dialog.add_item_shortcut("Ctrl+O", &open).unwrap();
+    assert_eq!(open.title(), "Open\tCtrl+O");
+
+    testing::press_key(&text, &"Ctrl+S".parse().unwrap());
+    testing::press_key(&text, &"Ctrl+O".parse().unwrap());
+    testing::press_key(&text, &"Ctrl+P".parse().unwrap());
+    assert!(dialog.remove_shortcut("Ctrl+S").unwrap());
+    assert!(!dialog.remove_shortcut("Ctrl+S").unwrap());
+    testing::press_key(&text, &"Ctrl+S".parse().unwrap());
+    assert_eq!(*log.borrow(), vec!["save", "open"]);
+    assert_eq!(dialog.shortcuts(), vec!["Ctrl+O".parse::<Key>().unwrap()]);
+
+    let log2 = log.clone();
+    dialog.add_shortcut("F5", move || log2.borrow_mut().push("refresh")).unwrap();
+    drop(open);
+    drop(text);
+    drop(dialog);
+    assert_eq!(Rc::strong_count(&log), 1);
+}
@@ -0,0 +1,52 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::testing;
+
+// Tests that a custom type of data is dragged between controls, and that a target doesn't accept
+// a type it wasn't given.
+
+#[derive(Debug, PartialEq)]
+struct Point(u8, u8);
+
+impl DragData for Point {
+    const TYPE: &'static str = "application/x-clear-coat-point";
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![self.0, self.1]
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Point> {
+        if bytes.len() == 2 { Some(Point(bytes[0], bytes[1])) } else { None }
+    }
+}
+
+#[test]
+fn test_drag_and_drop() {
+    let dropped = Rc::new(RefCell::new(vec![]));
+    let source = Canvas::new();
+    source.set_drag_source(|args: &DragBeginArgs| {
+        if args.x >= 0 { Some(Point(args.x as u8, args.y as u8)) } else { None }
+    });
+    let target = Label::new();
+    let dropped2 = dropped.clone();
+    target.add_drop_target(move |point: Point, args: &DropArgs| {
+        dropped2.borrow_mut().push((point, args.x, args.y));
+    });
+    let text_target = Text::new();
+    text_target.add_drop_target(|_: String, _: &DropArgs| panic!("wrong type dropped"));
+
+    assert!(testing::drag_and_drop(&source, 3, 4, &target, 30, 40));
+    assert!(!testing::drag_and_drop(&source, -1, 4, &target, 30, 40));
+    assert!(!testing::drag_and_drop(&source, 3, 4, &text_target, 30, 40));
+    assert_eq!(*dropped.borrow(), vec![(Point(3, 4), 30, 40)]);
+}
@@ -0,0 +1,46 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::testing;
+
+// Tests that dropped data only goes to a target for its type, and that data that can't be
+// converted to the type isn't passed on.
+
+#[derive(Debug, PartialEq)]
+struct Point(u8, u8);
+
+impl DragData for Point {
+    const TYPE: &'static str = "application/x-clear-coat-point";
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![self.0, self.1]
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Point> {
+        if bytes.len() == 2 { Some(Point(bytes[0], bytes[1])) } else { None }
+    }
+}
+
+#[test]
+fn test_drop_data_filtering() {
+    let dropped = Rc::new(RefCell::new(vec![]));
+    let target = Label::new();
+    let dropped2 = dropped.clone();
+    target.add_drop_target(move |point: Point, _: &DropArgs| dropped2.borrow_mut().push(point));
+
+    testing::drop_data(&target, "TEXT", &[1, 2], 0, 0);
+    testing::drop_data(&target, Point::TYPE, &[1, 2, 3], 0, 0);
+    assert!(dropped.borrow().is_empty());
+
+    testing::drop_data(&target, Point::TYPE, &[1, 2], 0, 0);
+    assert_eq!(*dropped.borrow(), vec![Point(1, 2)]);
+}
@@ -0,0 +1,40 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use clear_coat::testing;
+
+// Tests that dropped files are passed with their position and the number remaining, and that
+// returning `Ignore` skips the rest.
+
+#[test]
+fn test_drop_files() {
+    let dropped = Rc::new(RefCell::new(vec![]));
+    let dropped2 = dropped.clone();
+    let dialog = Dialog::new();
+    dialog.drop_files_event().add(move |args: &DropFilesArgs| {
+        dropped2.borrow_mut().push((args.path.clone(), args.x, args.y, args.remaining));
+        if args.path.ends_with("stop.txt") {
+            CallbackAction::Ignore
+        } else {
+            CallbackAction::Default
+        }
+    });
+    testing::drop_files(&dialog, &["/tmp/a.txt", "/tmp/b.txt"], 10, 20);
+    testing::drop_files(&dialog, &["/tmp/stop.txt", "/tmp/c.txt"], 0, 0);
+    assert_eq!(*dropped.borrow(), vec![
+        (PathBuf::from("/tmp/a.txt"), 10, 20, 1),
+        (PathBuf::from("/tmp/b.txt"), 10, 20, 0),
+        (PathBuf::from("/tmp/stop.txt"), 0, 0, 1),
+    ]);
+}
@@ -0,0 +1,27 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+
+// Tests that adding a drop files callback to a control that is already shown makes it accept
+// dropped files.
+
+#[test]
+fn test_drop_files_target() {
+    let dropfilestarget = Attr::<bool>::new("DROPFILESTARGET\0");
+    let text = Text::new();
+    let dialog = Dialog::with_child(&text);
+    dialog.show_xy(ScreenPosition::Center, ScreenPosition::Center).unwrap();
+    assert!(!text.get(dropfilestarget));
+
+    text.drop_files_event().add(|_: &DropFilesArgs| CallbackAction::Default);
+    assert!(text.get(dropfilestarget));
+    dialog.hide().unwrap();
+}
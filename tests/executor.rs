@@ -0,0 +1,72 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::{Duration, Instant};
+use clear_coat::*;
+use clear_coat::executor::{sleep, spawn_local, Sleep};
+
+// Tests that a task is woken by a timer and by a waker called from another thread.
+
+// Waits for `sleep`, then for another thread to wake it, then exits the main loop.
+struct SleepThenWakeFromThread {
+    sleep: Sleep,
+    woken: Option<Arc<AtomicBool>>,
+    done: Rc<Cell<bool>>,
+}
+
+impl Future for SleepThenWakeFromThread {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if Pin::new(&mut self.sleep).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        match self.woken.clone() {
+            None => {
+                let woken = Arc::new(AtomicBool::new(false));
+                let woken2 = woken.clone();
+                let waker = cx.waker().clone();
+                thread::spawn(move || {
+                    woken2.store(true, Ordering::SeqCst);
+                    waker.wake();
+                });
+                self.woken = Some(woken);
+                Poll::Pending
+            },
+            Some(ref woken) if woken.load(Ordering::SeqCst) => {
+                self.done.set(true);
+                exit_loop();
+                Poll::Ready(())
+            },
+            Some(_) => Poll::Pending,
+        }
+    }
+}
+
+#[test]
+fn test_executor() {
+    let done = Rc::new(Cell::new(false));
+    let start = Instant::now();
+    spawn_local(SleepThenWakeFromThread {
+        sleep: sleep(Duration::from_millis(50)),
+        woken: None,
+        done: done.clone(),
+    });
+    main_loop();
+    assert!(done.get());
+    assert!(start.elapsed() >= Duration::from_millis(50));
+}
@@ -0,0 +1,81 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use clear_coat::executor::spawn_local;
+use clear_coat::testing;
+
+// Tests that `Button::clicked` and `Dialog::closed` complete when the button is clicked and the
+// dialog is hidden, and not before.
+
+// Waits for the button to be clicked, then for the dialog to close, then exits the main loop.
+// Each event is simulated after the future waiting for it is first polled.
+struct ClickThenClose {
+    button: Button,
+    dialog: Dialog,
+    clicked: Option<ButtonClicked>,
+    closed: Option<DialogClosed>,
+    log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Future for ClickThenClose {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let this = &mut *self;
+        if let Some(mut clicked) = this.clicked.take() {
+            if Pin::new(&mut clicked).poll(cx).is_pending() {
+                this.log.borrow_mut().push("waiting for click");
+                this.clicked = Some(clicked);
+                testing::click(&this.button);
+                return Poll::Pending;
+            }
+            this.log.borrow_mut().push("clicked");
+            this.closed = Some(this.dialog.closed());
+        }
+        if let Some(mut closed) = this.closed.take() {
+            if Pin::new(&mut closed).poll(cx).is_pending() {
+                this.log.borrow_mut().push("waiting for close");
+                this.closed = Some(closed);
+                this.dialog.hide().unwrap();
+                return Poll::Pending;
+            }
+            this.log.borrow_mut().push("closed");
+        }
+        exit_loop();
+        Poll::Ready(())
+    }
+}
+
+#[test]
+fn test_executor_control_futures() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let button = Button::new();
+    let dialog = Dialog::with_child(&button);
+    spawn_local(ClickThenClose {
+        button: button.clone(),
+        dialog: dialog.clone(),
+        clicked: Some(button.clicked()),
+        closed: None,
+        log: log.clone(),
+    });
+    // Hiding the dialog would otherwise exit the main loop before the task is polled again.
+    set_lock_loop(true);
+    dialog.show().unwrap();
+    main_loop();
+    set_lock_loop(false);
+    assert_eq!(*log.borrow(),
+               vec!["waiting for click", "clicked", "waiting for close", "closed"]);
+}
@@ -0,0 +1,38 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#[macro_use]
+extern crate clear_coat;
+
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use clear_coat::led::{self, LayoutNode};
+
+// Tests that exported LED loads back into the same layout.
+
+#[test]
+fn test_export_led() {
+    let button = Button::new();
+    button.set_title("Say \"hi\"");
+    let text = Text::new();
+    text.set_name("message");
+    let vbox = vbox!(&button, &text);
+    vbox.set_gap(10);
+
+    let node = LayoutNode::from_control(&vbox);
+    assert_eq!(node.class_name, "vbox");
+    assert_eq!(node.attribute("GAP"), Some("10"));
+    assert_eq!(node.children.len(), 2);
+    assert_eq!(node.children[0].attribute("TITLE"), Some("Say \"hi\""));
+    assert!(node.to_string().starts_with("vbox [GAP=\"10\"]\n  button"));
+
+    let exported = led::export(&vbox, "layout");
+    assert!(!exported.contains("NULL"));
+    let loaded = led::load_buffer(&exported).unwrap();
+    let loaded_vbox = loaded.get::<Vbox>("layout").unwrap();
+    assert_eq!(LayoutNode::from_control(&loaded_vbox), node);
+}
@@ -0,0 +1,34 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use clear_coat::led;
+
+// Tests that images used by exported controls are exported too.
+
+#[test]
+fn test_export_led_images() {
+    let palette = [None, Some((255, 0, 0))];
+    let image = Image::from_indexed(2, 2, &[1, 0, 0, 1], &palette);
+    let button = Button::new();
+    button.set_title("Red");
+    button.set_image(Some(&image));
+
+    let exported = led::export(&button, "red");
+    assert!(exported.starts_with("red_image1 = IMAGE["));
+    assert!(exported.contains("IMAGE=\"red_image1\""));
+
+    let loaded = led::load_buffer(&exported).unwrap();
+    let loaded_button = loaded.get::<Button>("red").unwrap();
+    let loaded_image = loaded_button.image().unwrap();
+    assert_eq!(loaded_image.format(), ImageFormat::Indexed);
+    assert_eq!(loaded_image.pixels(), image.pixels());
+    assert_eq!(loaded_image.palette_color(1), Some((255, 0, 0)));
+}
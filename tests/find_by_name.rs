@@ -0,0 +1,40 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#[macro_use]
+extern crate clear_coat;
+
+use clear_coat::*;
+
+// Tests finding named controls in a dialog and globally, including the errors for a name that
+// isn't used and for a control of the wrong type.
+
+#[test]
+fn test_find_by_name() {
+    let email = Text::new();
+    email.set_name("email");
+    let ok = Button::new();
+    ok.set_global_name("ok_button");
+    let dialog = Dialog::with_child(&vbox!(&email, &ok));
+
+    dialog.find::<Text>("email").unwrap().set_value("a@example.com");
+    assert_eq!(email.value(), "a@example.com");
+    assert_eq!(email.name(), "email");
+
+    assert_eq!(dialog.find::<Text>("phone").err().unwrap(),
+               Error::NameNotFound { name: "phone".to_owned() });
+    assert_eq!(dialog.find::<List>("email").err().unwrap(), Error::WrongClass {
+        name: "email".to_owned(),
+        expected: "list",
+        actual: "text".to_owned(),
+    });
+
+    assert!(find_global::<Button>("ok_button").is_ok());
+    assert_eq!(ok.global_name(), Some("ok_button".to_owned()));
+    remove_global_name("ok_button");
+    assert!(find_global::<Button>("ok_button").is_err());
+}
@@ -0,0 +1,23 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::thread;
+use clear_coat::*;
+
+// Tests that the first thread to use IUP is the only one allowed to, even if it isn't the thread
+// that runs the test.
+
+#[test]
+#[should_panic(expected="IUP/Clear Coat functions must be called from a single thread")]
+fn test_first_thread_wins() {
+    thread::spawn(|| {
+        let _ = Button::new();
+    }).join().expect("first thread panicked");
+    let _ = Button::new();
+}
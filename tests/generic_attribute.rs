@@ -0,0 +1,36 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+use clear_coat::attr;
+use clear_coat::common_attrs_cbs::*;
+
+// Tests that `Control::get` and `Control::set` convert values the same way as the attribute's
+// own methods, including for a descriptor made outside the crate.
+
+const READONLY: Attr<bool> = Attr::new("READONLY\0");
+
+#[test]
+fn test_generic_attribute() {
+    let text = Text::new();
+    text.set(attr::EXPAND, Expand::Horizontal);
+    assert_eq!(text.expand(), Expand::Horizontal);
+    text.set_visible_columns(12);
+    assert_eq!(text.get(attr::VISIBLECOLUMNS), 12);
+
+    text.set(READONLY, true);
+    assert_eq!(text.get(READONLY), true);
+    assert_eq!(READONLY.name(), "READONLY");
+    text.reset(READONLY);
+    assert_eq!(text.get(READONLY), false);
+
+    assert_eq!(<(u32, u32)>::parse_attr("30x20"), Some((30, 20)));
+    assert_eq!(<(i32, i32)>::parse_attr("-1:5"), Some((-1, 5)));
+    assert_eq!(bool::parse_attr("maybe"), None);
+}
@@ -0,0 +1,32 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::globals;
+
+// Tests that the global attributes can be read and parsed.
+
+#[test]
+fn test_globals() {
+    let (width, height) = globals::screen_size();
+    assert!(width > 0 && height > 0);
+    let (full_width, full_height) = globals::full_size();
+    assert!(full_width >= width && full_height >= height);
+    assert!(globals::screen_dpi() > 0.0);
+
+    for monitor in globals::monitors_info() {
+        assert!(monitor.width > 0 && monitor.height > 0);
+    }
+    let virtual_screen = globals::virtual_screen();
+    assert!(virtual_screen.width >= full_width);
+
+    assert!(!globals::system().is_empty());
+    assert!(!globals::driver().is_empty());
+    assert!(globals::iup_version_number() >= 312000);
+    assert!(globals::iup_version().starts_with("3."));
+}
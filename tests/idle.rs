@@ -0,0 +1,32 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use clear_coat::*;
+
+// Tests that idle functions run until they return false and that a removed one never runs.
+
+#[test]
+fn test_idle() {
+    let count = Rc::new(Cell::new(0));
+    let count2 = count.clone();
+    on_idle(move || {
+        count2.set(count2.get() + 1);
+        if count2.get() == 3 {
+            exit_loop();
+        }
+        count2.get() < 3
+    });
+    let removed = on_idle(|| panic!("removed idle function was called"));
+    remove_idle(removed);
+
+    main_loop();
+    assert_eq!(count.get(), 3);
+}
@@ -0,0 +1,50 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+
+// Tests that images keep their pixels, palette, and hotspot, and that controls keep the images
+// set on them alive.
+
+#[test]
+fn test_image() {
+    let palette = [None, Some((255, 0, 0)), Some((0, 0, 255))];
+    let image = Image::from_indexed(3, 2, &[0, 1, 2, 2, 1, 0], &palette);
+    assert_eq!(image.format(), ImageFormat::Indexed);
+    assert_eq!((image.width(), image.height()), (3, 2));
+    assert_eq!(image.pixels(), vec![0, 1, 2, 2, 1, 0]);
+    assert_eq!(image.palette_color(0), None);
+    assert_eq!(image.palette_color(2), Some((0, 0, 255)));
+    assert_eq!(image.hotspot(), (0, 0));
+    image.set_hotspot(1, 1);
+    assert_eq!(image.hotspot(), (1, 1));
+
+    let rgb = Image::from_rgb(1, 1, &[10, 20, 30]);
+    assert_eq!(rgb.format(), ImageFormat::Rgb);
+    assert_eq!(rgb.pixels(), vec![10, 20, 30]);
+
+    let button = Button::new();
+    button.set_image(Some(&image));
+    let dialog = Dialog::new();
+    dialog.set_icon(Some(&rgb));
+    let tabs = Tabs::new();
+    tabs.append_tabs(&[TabInfo::new(&Label::new()).title("First").image(&rgb)]);
+    drop(image);
+    drop(rgb);
+
+    let image = button.image().unwrap();
+    assert_eq!(image.pixels(), vec![0, 1, 2, 2, 1, 0]);
+    assert_eq!(dialog.icon().unwrap().pixels(), vec![10, 20, 30]);
+    assert_eq!(tabs.tab_image(0).unwrap().pixels(), vec![10, 20, 30]);
+    assert!(button.pressed_image().is_none());
+
+    button.set_image(None);
+    assert!(button.image().is_none());
+}
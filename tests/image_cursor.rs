@@ -0,0 +1,38 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+
+// Tests that a control keeps an image set as its cursor alive and that reading the cursor returns
+// the same image.
+
+#[test]
+fn test_image_cursor() {
+    let canvas = Canvas::new();
+    let image = Image::from_rgba(2, 2, &[255; 16]);
+    image.set_hotspot(1, 0);
+    let image_handle = image.handle();
+    canvas.set_cursor(Cursor::Image(image));
+
+    match canvas.cursor() {
+        Cursor::Image(image) => {
+            assert_eq!(image.handle(), image_handle);
+            assert_eq!(image.hotspot(), (1, 0));
+            assert_eq!(image.pixels(), vec![255; 16]);
+        },
+        cursor => panic!("expected an image cursor, but got {:?}", cursor),
+    }
+
+    canvas.set_cursor(Cursor::Hand);
+    match canvas.cursor() {
+        Cursor::Hand => {},
+        cursor => panic!("expected the hand cursor, but got {:?}", cursor),
+    }
+}
@@ -0,0 +1,54 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#![cfg(feature = "image-files")]
+
+extern crate clear_coat;
+extern crate image;
+
+use std::env;
+use std::fs;
+use std::io::Cursor;
+use clear_coat::*;
+use image::{ImageFormat as FileFormat, RgbaImage};
+
+// Tests that PNG, BMP, and ICO files are decoded into RGBA images, from memory and from a file,
+// and that invalid data is an error.
+
+fn encode(image: &RgbaImage, format: FileFormat) -> Vec<u8> {
+    let mut bytes = Cursor::new(vec![]);
+    image.write_to(&mut bytes, format).unwrap();
+    bytes.into_inner()
+}
+
+#[test]
+fn test_image_files() {
+    let pixels = vec![255, 0, 0, 255, 0, 255, 0, 128, 0, 0, 255, 0, 9, 9, 9, 255];
+    let original = RgbaImage::from_raw(2, 2, pixels.clone()).unwrap();
+
+    let png = Image::from_png_bytes(&encode(&original, FileFormat::Png)).unwrap();
+    assert_eq!(png.format(), ImageFormat::Rgba);
+    assert_eq!((png.width(), png.height()), (2, 2));
+    assert_eq!(png.pixels(), pixels);
+
+    let bmp = Image::from_bmp_bytes(&encode(&original, FileFormat::Bmp)).unwrap();
+    assert_eq!(bmp.pixels(), pixels);
+
+    let ico = Image::from_ico_bytes(&encode(&original, FileFormat::Ico)).unwrap();
+    assert_eq!(ico.pixels(), pixels);
+
+    let path = env::temp_dir().join("clear_coat_test_image_files.png");
+    fs::write(&path, encode(&original, FileFormat::Png)).unwrap();
+    let loaded = Image::load(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap().pixels(), pixels);
+
+    match Image::from_png_bytes(b"not a png") {
+        Err(Error::ImageFile { .. }) => {},
+        _ => panic!("expected an ImageFile error"),
+    }
+}
@@ -0,0 +1,32 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+extern crate iup_sys;
+
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use iup_sys::*;
+
+// Tests that a `try_` getter returns the attribute's name and value instead of panicking when
+// the value is unknown.
+
+#[test]
+fn test_invalid_attribute() {
+    let canvas = Canvas::new();
+    unsafe {
+        IupSetStrAttribute(canvas.handle(),
+                           "CURSOR\0".as_ptr() as *const i8,
+                           "NOT_A_CURSOR\0".as_ptr() as *const i8);
+    }
+    let err = canvas.try_cursor().unwrap_err();
+    assert_eq!(err, Error::InvalidAttribute {
+        name: "CURSOR".to_owned(),
+        value: "NOT_A_CURSOR".to_owned(),
+    });
+    assert_eq!(err.to_string(), "invalid value \"NOT_A_CURSOR\" for attribute CURSOR");
+}
@@ -0,0 +1,41 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use clear_coat::testing;
+
+// Tests that keys are passed on to the dialog's K_ANY callback unless the focused control's
+// callback handles them.
+
+#[test]
+fn test_k_any_event() {
+    let keys = Rc::new(RefCell::new(vec![]));
+    let text = Text::new();
+    let dialog = Dialog::with_child(&text);
+    let keys2 = keys.clone();
+    text.k_any_event().add(move |key: &Key| {
+        keys2.borrow_mut().push(format!("text {}", key));
+        if key.ctrl { CallbackAction::Continue } else { CallbackAction::Default }
+    });
+    let keys2 = keys.clone();
+    dialog.k_any_event().add(move |key: &Key| {
+        keys2.borrow_mut().push(format!("dialog {}", key));
+        CallbackAction::Ignore
+    });
+
+    match testing::press_key(&text, &"Ctrl+S".parse().unwrap()) {
+        CallbackAction::Ignore => {},
+        _ => panic!("wrong callback action"),
+    }
+    testing::press_key(&text, &Key::new(KeyCode::Char('S')));
+    assert_eq!(*keys.borrow(), vec!["text Ctrl+S", "dialog Ctrl+S", "text S"]);
+}
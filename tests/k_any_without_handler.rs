@@ -0,0 +1,40 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#[macro_use]
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use clear_coat::testing;
+
+// Tests that a key pressed in a control without a K_ANY callback is passed on through its parents
+// to the dialog.
+
+#[test]
+fn test_k_any_without_handler() {
+    let keys = Rc::new(RefCell::new(vec![]));
+    let button = Button::new();
+    let dialog = Dialog::with_child(&vbox!(&button));
+    match testing::press_key(&button, &Key::new(KeyCode::Enter)) {
+        CallbackAction::Default => {},
+        _ => panic!("wrong callback action"),
+    }
+
+    let keys2 = keys.clone();
+    dialog.k_any_event().add(move |key: &Key| {
+        keys2.borrow_mut().push(key.to_string());
+        CallbackAction::Ignore
+    });
+    match testing::press_key(&button, &"Alt+X".parse().unwrap()) {
+        CallbackAction::Ignore => {},
+        _ => panic!("wrong callback action"),
+    }
+    assert_eq!(*keys.borrow(), vec!["Alt+X"]);
+}
@@ -0,0 +1,37 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+
+// Tests that keys are decoded from IUP's key codes and converted to and from text.
+
+#[test]
+fn test_key_parsing() {
+    // K_cS, K_S, K_s, K_sHOME, K_F5, and Alt+'1'
+    let ctrl_s = Key::from_iup_code(0x2000_0000 | 'S' as u32);
+    assert_eq!(ctrl_s, Key::new(KeyCode::Char('S')).ctrl());
+    assert_eq!(Key::from_iup_code('S' as u32), Key::new(KeyCode::Char('S')).shift());
+    assert_eq!(Key::from_iup_code('s' as u32), Key::new(KeyCode::Char('S')));
+    assert_eq!(Key::from_iup_code(0x1000_FF50), Key::new(KeyCode::Home).shift());
+    assert_eq!(Key::from_iup_code(0xFFC2), Key::new(KeyCode::F(5)));
+    assert_eq!(Key::from_iup_code(0x4000_0000 | '1' as u32).to_string(), "Alt+1");
+
+    for &code in &[0x2000_0000 | 'S' as u32, 'S' as u32, 's' as u32, 0x1000_FF50, 0xFFFF, 0xFF0D] {
+        assert_eq!(Key::from_iup_code(code).to_iup_code(), code);
+    }
+
+    assert_eq!("Ctrl+Shift+S".parse::<Key>().unwrap().to_string(), "Ctrl+Shift+S");
+    assert_eq!("shift + control + s".parse::<Key>().unwrap().to_string(), "Ctrl+Shift+S");
+    assert_eq!("Ctrl++".parse::<Key>().unwrap(), Key::new(KeyCode::Char('+')).ctrl());
+    assert_eq!("esc".parse::<Key>().unwrap(), Key::new(KeyCode::Escape));
+    assert_eq!("Alt+F4".parse::<Key>().unwrap(), Key::new(KeyCode::F(4)).alt());
+    assert_eq!(Key::new(KeyCode::Char('A')).to_char(), Some('a'));
+    assert!("Hyper+S".parse::<Key>().is_err());
+    assert!("Ctrl+Nothing".parse::<Key>().is_err());
+}
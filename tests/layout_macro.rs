@@ -0,0 +1,46 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#[macro_use]
+extern crate clear_coat;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+
+// Tests that `layout!` sets attributes, adds handlers, appends children in order, and binds
+// nodes to variables that outlive the macro.
+
+#[test]
+fn test_layout_macro() {
+    let clicked = Rc::new(Cell::new(false));
+    let clicked2 = clicked.clone();
+    layout!(Dialog as dialog {
+        .set_title("Form"),
+        Vbox as vbox {
+            .set_gap(10),
+            .set(attr::EXPAND, Expand::Horizontal),
+            Label { .set_title("Email:") },
+            Text as email,
+            (fill!()),
+            Button as ok {
+                .set_title("OK"),
+                action_event => move || clicked2.set(true),
+            }
+        },
+    });
+
+    assert_eq!(dialog.title(), "Form");
+    assert_eq!(vbox.gap(), 10);
+    assert_eq!(vbox.expand(), Expand::Horizontal);
+    let class_names: Vec<_> = vbox.children().map(|c| c.class_name()).collect();
+    assert_eq!(class_names, ["label", "text", "fill", "button"]);
+    assert!(match email.parent() { Some(AnyControl::Vbox(_)) => true, _ => false });
+    assert_eq!(ok.title(), "OK");
+    assert!(!clicked.get());
+}
@@ -0,0 +1,41 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+use clear_coat::led;
+
+// Tests that named elements loaded from LED come back as the right wrappers, that they are
+// destroyed with the last reference, and that syntax errors are returned.
+
+#[test]
+fn test_load_led() {
+    let text = {
+        let led = led::load_buffer(r#"
+            name = TEXT[VALUE="Jordan"](do_nothing)
+            dlg = DIALOG[TITLE="Form"](HBOX(LABEL("Name:"), name))
+        "#).unwrap();
+        let mut names = led.names();
+        names.sort();
+        assert_eq!(names, ["dlg", "name"]);
+
+        let dialog = led.get::<Dialog>("dlg").unwrap();
+        assert!(match dialog.children().next() { Some(AnyControl::Hbox(_)) => true, _ => false });
+        assert!(led.get::<Button>("name").is_err());
+        led.get::<Text>("name").unwrap()
+    };
+    // The dialog was destroyed with its last reference, but the text box was detached from it
+    // because it is still referenced.
+    assert!(find_global::<Dialog>("dlg").is_err());
+    assert_eq!(text.value(), "Jordan");
+
+    match led::load_buffer("dlg = DIALOG(") {
+        Err(Error::Led { message }) => assert!(!message.is_empty()),
+        _ => panic!("expected a LED error"),
+    }
+}
@@ -0,0 +1,38 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::panic;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use clear_coat::*;
+
+// Tests that stepping the loop runs posted closures, that `exit_loop` makes it return false,
+// and that a panic in a closure is propagated out of it.
+
+#[test]
+fn test_loop_step() {
+    let sender = UiSender::new();
+    let ran = Arc::new(AtomicBool::new(false));
+    let ran2 = ran.clone();
+    sender.post(move || ran2.store(true, Ordering::SeqCst)).ok().unwrap();
+    while !ran.load(Ordering::SeqCst) {
+        assert!(loop_step_wait());
+    }
+    flush();
+
+    sender.post(|| exit_loop()).ok().unwrap();
+    while loop_step_wait() {}
+
+    sender.post(|| panic!("panic while {}", "stepping")).ok().unwrap();
+    let result = panic::catch_unwind(|| {
+        while loop_step_wait() {}
+    });
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<String>().map(|s| &s[..]), Some("panic while stepping"));
+}
@@ -0,0 +1,40 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::panic;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use clear_coat::*;
+
+// Tests that a panic with a payload that isn't a string, from a callback run by a nested `popup`
+// loop, ends every loop and is propagated out of `main_loop` with the same payload.
+
+#[derive(Debug, PartialEq)]
+struct Payload(i32);
+
+#[test]
+fn test_panic_payload_nested_popup() {
+    let after_popup = Arc::new(AtomicBool::new(false));
+    let after_popup2 = after_popup.clone();
+    UiSender::new().post(move || {
+        let outer = Dialog::new();
+        UiSender::new().post(|| {
+            let inner = Dialog::new();
+            UiSender::new().post(|| panic::panic_any(Payload(42))).ok().unwrap();
+            let _ = inner.popup(ScreenPosition::Center, ScreenPosition::Center);
+        }).ok().unwrap();
+        let _ = outer.popup(ScreenPosition::Center, ScreenPosition::Center);
+        after_popup2.store(true, Ordering::SeqCst);
+    }).ok().unwrap();
+
+    let result = panic::catch_unwind(|| main_loop());
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<Payload>(), Some(&Payload(42)));
+    assert!(!after_popup.load(Ordering::SeqCst));
+}
@@ -0,0 +1,57 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#[macro_use]
+extern crate clear_coat;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use clear_coat::*;
+
+// Tests that bound controls are set from their properties when bound and when the properties
+// change, that subscribers are only notified of changes, and that destroyed controls are unbound.
+
+#[test]
+fn test_property_binding() {
+    let name = Property::new("first".to_owned());
+    let text = Text::new();
+    text.bind_value(&name);
+    assert_eq!(text.value(), "first");
+    let notified = Rc::new(Cell::new(0));
+    let notified2 = notified.clone();
+    let token = name.subscribe(move |_| notified2.set(notified2.get() + 1));
+    name.set("second".to_owned());
+    name.set("second".to_owned());
+    assert_eq!(text.value(), "second");
+    assert_eq!(notified.get(), 1);
+    name.unsubscribe(token);
+    drop(text);
+    name.set("third".to_owned());
+    assert_eq!(notified.get(), 1);
+
+    let on = Property::new(true);
+    let toggle = Toggle::new();
+    toggle.bind_on(&on);
+    assert!(toggle.is_on());
+    on.set(false);
+    assert!(!toggle.is_on());
+
+    let selection = Property::new(None);
+    let list = List::new();
+    list.set_items(&["a", "b", "c"]);
+    list.bind_value_single(&selection);
+    selection.set(Some(2));
+    assert_eq!(list.value_single(), Some(2));
+
+    let tab = Property::new(0);
+    let tabs = Tabs::new();
+    tabs.append_tabs(&[&TabInfo::new(&Label::new()), &TabInfo::new(&Label::new())]);
+    let _dialog = Dialog::with_child(&vbox!(&tabs, &list));
+    tabs.bind_value_pos(&tab);
+    tab.set(1);
+    assert_eq!(tabs.value_pos(), 1);
+}
@@ -0,0 +1,54 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#[macro_use]
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::testing;
+
+// Tests that properties are updated when the user changes the controls bound to them, and that
+// their subscribers are notified.
+
+#[test]
+fn test_property_user_edits() {
+    let name = Property::new(String::new());
+    let text = Text::new();
+    text.bind_value(&name);
+    testing::type_text(&text, "hi");
+    assert_eq!(name.get(), "hi");
+
+    let on = Property::new(false);
+    let toggle = Toggle::new();
+    toggle.bind_on(&on);
+    let changes = Rc::new(RefCell::new(vec![]));
+    let changes2 = changes.clone();
+    on.subscribe(move |&on| changes2.borrow_mut().push(on));
+    testing::toggle(&toggle);
+    assert!(on.get());
+    testing::toggle(&toggle);
+    assert!(!on.get());
+    assert_eq!(*changes.borrow(), vec![true, false]);
+
+    let selection = Property::new(Some(0));
+    let list = List::new();
+    list.set_items(&["a", "b", "c"]);
+    list.bind_value_single(&selection);
+    testing::choose_item(&list, 2);
+    assert_eq!(selection.get(), Some(2));
+
+    let tab = Property::new(0);
+    let tabs = Tabs::new();
+    tabs.append_tabs(&[&TabInfo::new(&Label::new()), &TabInfo::new(&Label::new())]);
+    let _dialog = Dialog::with_child(&vbox!(&tabs, &list, &text, &toggle));
+    tabs.bind_value_pos(&tab);
+    testing::change_tab(&tabs, 1);
+    assert_eq!(tab.get(), 1);
+    assert_eq!(tabs.value_pos(), 1);
+}
@@ -0,0 +1,38 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::rc::Rc;
+use clear_coat::*;
+
+// Tests that dropping a guard removes its callback, and that dropping it after the control is
+// destroyed does nothing.
+
+#[test]
+fn test_scoped_callback() {
+    let x = Rc::new(0);
+    let button = Button::new();
+    let x2 = x.clone();
+    let guard = button.action_event().add_scoped(move || println!("{}", *x2));
+    let x3 = x.clone();
+    let _token = button.action_event().add(move || println!("{}", *x3));
+    assert_eq!(Rc::strong_count(&x), 3);
+    drop(guard);
+    assert_eq!(Rc::strong_count(&x), 2);
+
+    let x2 = x.clone();
+    let guard = button.action_event().add_scoped(move || println!("{}", *x2));
+    drop(button);
+    assert_eq!(Rc::strong_count(&x), 1);
+    // A new control could have the same handle as the destroyed one.
+    let button = Button::new();
+    let x2 = x.clone();
+    button.action_event().add(move || println!("{}", *x2));
+    drop(guard);
+    assert_eq!(Rc::strong_count(&x), 2);
+}
@@ -0,0 +1,24 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::thread;
+use clear_coat::*;
+
+// Tests that using IUP from a second thread panics after the first thread has used it.
+
+#[test]
+fn test_second_thread_panics() {
+    let _button = Button::new();
+    let result = thread::spawn(|| {
+        let _ = Button::new();
+    }).join();
+    let payload = result.expect_err("second thread did not panic");
+    let msg = payload.downcast_ref::<&'static str>().expect("panic payload was not a &str");
+    assert_eq!(*msg, "IUP/Clear Coat functions must be called from a single thread");
+}
@@ -0,0 +1,80 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use clear_coat::*;
+use clear_coat::common_attrs_cbs::*;
+use clear_coat::testing;
+
+// Tests that simulated events call the callbacks with the same arguments as real events, and
+// update bound properties.
+
+#[test]
+fn test_simulate_events() {
+    let log = Rc::new(RefCell::new(vec![]));
+
+    let button = Button::new();
+    let log2 = log.clone();
+    button.action_event().add(move || log2.borrow_mut().push("clicked".to_owned()));
+    testing::click(&button);
+
+    let toggle = Toggle::new();
+    let log2 = log.clone();
+    toggle.action_event().add(move |on| log2.borrow_mut().push(format!("toggled {}", on)));
+    testing::toggle(&toggle);
+    testing::set_toggle(&toggle, true);
+    assert!(toggle.is_on());
+
+    let list = List::new();
+    list.set_items(&["a", "b"]);
+    let log2 = log.clone();
+    list.action_event().add(move |args: &ListActionArgs| {
+        log2.borrow_mut().push(format!("{} {} {}", args.text, args.item_index, args.selected));
+    });
+    testing::choose_item(&list, 0);
+    testing::choose_item(&list, 1);
+    assert_eq!(list.value_single(), Some(1));
+
+    let name = Property::new(String::new());
+    let text = Text::new();
+    text.bind_value(&name);
+    testing::type_text(&text, "hi");
+    assert_eq!(name.get(), "hi");
+
+    let canvas = Canvas::new();
+    let log2 = log.clone();
+    canvas.button_event().add(move |args: &ButtonArgs| {
+        log2.borrow_mut().push(format!("button {} {} {}", args.pressed, args.x, args.y));
+        if args.status.shift_pressed { CallbackAction::Ignore } else { CallbackAction::Default }
+    });
+    let log2 = log.clone();
+    canvas.motion_event().add(move |args: &MotionArgs| {
+        let pressed = args.status.button1_pressed;
+        log2.borrow_mut().push(format!("motion {} {} {}", args.x, args.y, pressed));
+    });
+    let status = KeyboardMouseStatus { shift_pressed: true, ..Default::default() };
+    let args = ButtonArgs::new(MouseButton::Button1, true, 5, 6, status);
+    match testing::send_button(&canvas, &args) {
+        CallbackAction::Ignore => {},
+        _ => panic!("wrong callback action"),
+    }
+    let status = KeyboardMouseStatus { button1_pressed: true, ..Default::default() };
+    testing::send_motion(&canvas, &MotionArgs::new(7, 8, status));
+
+    assert_eq!(*log.borrow(), vec![
+        "clicked",
+        "toggled true",
+        "a 0 true",
+        "a 0 false",
+        "b 1 true",
+        "button true 5 6",
+        "motion 7 8 true",
+    ]);
+}
@@ -0,0 +1,21 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+use clear_coat::testing;
+
+// Tests that a panic in a callback called by a simulated event is propagated out of it.
+
+#[test]
+#[should_panic(expected="clicked")]
+fn test_simulated_event_panics() {
+    let button = Button::new();
+    button.action_event().add(|| panic!("clicked"));
+    testing::click(&button);
+}
@@ -0,0 +1,32 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use clear_coat::*;
+
+// Tests that a tab's image stays alive when tabs are inserted before it and the image at its old
+// position is changed.
+
+#[test]
+fn test_tab_image_refs() {
+    let first = Text::new();
+    let second = Text::new();
+    let tabs = Tabs::new();
+    tabs.append_tabs(&[&TabInfo::new(&first), &TabInfo::new(&second)]);
+    let second_pixels = {
+        let image = Image::from_rgb(1, 1, &[0, 0, 255]);
+        tabs.set_tab_image(1, Some(&image));
+        image.pixels()
+    };
+
+    tabs.insert(Some(&first), &Text::new()).unwrap();
+    tabs.set_tab_image(1, Some(&Image::from_rgb(1, 1, &[255, 0, 0])));
+
+    assert_eq!(tabs.tab_image(2).unwrap().pixels(), second_pixels);
+    assert_eq!(tabs.tab_image(1).unwrap().pixels(), vec![255, 0, 0]);
+}
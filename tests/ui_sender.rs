@@ -0,0 +1,35 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::sync::mpsc;
+use std::thread;
+use clear_coat::*;
+
+// Tests that a closure posted from another thread runs on the UI thread and that posting fails
+// after the main loop has exited.
+
+#[test]
+fn test_ui_sender() {
+    let ui_thread = thread::current().id();
+    let (tx, rx) = mpsc::channel();
+    let sender = UiSender::new();
+    let sender2 = sender.clone();
+    let worker = thread::spawn(move || {
+        sender2.post(move || {
+            tx.send(thread::current().id()).unwrap();
+            exit_loop();
+        }).expect("failed to post closure");
+    });
+    main_loop();
+    worker.join().unwrap();
+    assert_eq!(rx.try_recv().expect("posted closure didn't run"), ui_thread);
+
+    let closed = thread::spawn(move || sender.post(|| {}).is_err()).join().unwrap();
+    assert!(closed, "posting after the main loop exited succeeded");
+}
@@ -0,0 +1,39 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+extern crate clear_coat;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use clear_coat::*;
+
+thread_local!(static RAN: RefCell<Vec<&'static str>> = RefCell::new(vec![]));
+
+// Tests that when the panic hook handles a panic in a posted closure, the closures posted after
+// it still run.
+
+#[test]
+fn test_ui_sender_handled_panic() {
+    let handled = Rc::new(RefCell::new(0));
+    let handled2 = handled.clone();
+    set_callback_panic_hook(move |_| {
+        *handled2.borrow_mut() += 1;
+        true
+    });
+
+    let sender = UiSender::new();
+    sender.post(|| panic!("first")).ok().unwrap();
+    sender.post(|| {
+        RAN.with(|ran| ran.borrow_mut().push("second"));
+        exit_loop();
+    }).ok().unwrap();
+    main_loop();
+
+    assert_eq!(*handled.borrow(), 1);
+    RAN.with(|ran| assert_eq!(*ran.borrow(), vec!["second"]));
+    clear_callback_panic_hook();
+}
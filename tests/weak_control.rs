@@ -0,0 +1,35 @@
+/* Copyright 2016 Jordan Miner
+ *
+ * Licensed under the MIT license <LICENSE or
+ * http://opensource.org/licenses/MIT>. This file may not be copied,
+ * modified, or distributed except according to those terms.
+ */
+
+#[macro_use]
+extern crate clear_coat;
+extern crate iup_sys;
+
+use clear_coat::*;
+use iup_sys::*;
+
+// Tests that a weak reference can be upgraded while the control exists, including when it has no
+// wrappers, and not once it has been destroyed either by dropping or by `IupDestroy`.
+
+#[test]
+fn test_weak_control() {
+    let button = Button::new();
+    let weak_button = button.downgrade();
+    assert_eq!(weak_button.upgrade().map(|b| b.handle()), Some(button.handle()));
+    drop(button);
+    assert!(weak_button.upgrade().is_none());
+
+    let label = Label::new();
+    let weak_label = label.downgrade();
+    let vbox = vbox!(&label);
+    drop(label);
+    assert!(weak_label.upgrade().is_some());
+    let weak_vbox = vbox.downgrade();
+    unsafe { IupDestroy(vbox.handle()); }
+    assert!(weak_label.upgrade().is_none());
+    assert!(weak_vbox.upgrade().is_none());
+}